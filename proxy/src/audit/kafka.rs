@@ -0,0 +1,78 @@
+use super::{AuditEvent, AuditSink};
+use async_trait::async_trait;
+use ramparts_common::anyhow::Result;
+
+/// Publishes audit events to a Kafka topic, keyed by `request_id`.
+///
+/// Kafka support is gated behind the `kafka` feature so deployments that don't
+/// need it avoid the `rdkafka`/librdkafka build dependency.
+#[cfg(feature = "kafka")]
+pub struct KafkaSink {
+    topic: String,
+    producer: rdkafka::producer::FutureProducer,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaSink {
+    pub fn new(brokers: String, topic: String) -> Result<Self> {
+        use rdkafka::config::ClientConfig;
+        use ramparts_common::anyhow::anyhow;
+
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .set("message.timeout.ms", "5000")
+            .create()
+            .map_err(|e| anyhow!("failed to create Kafka producer: {}", e))?;
+        Ok(Self { topic, producer })
+    }
+}
+
+#[cfg(feature = "kafka")]
+#[async_trait]
+impl AuditSink for KafkaSink {
+    async fn publish(&self, event: &AuditEvent) -> Result<()> {
+        use rdkafka::producer::{FutureRecord, Producer};
+        use ramparts_common::anyhow::anyhow;
+        use std::time::Duration;
+
+        let payload = serde_json::to_vec(event)?;
+        let record = FutureRecord::to(&self.topic)
+            .key(&event.request_id)
+            .payload(&payload);
+        self.producer
+            .send(record, Duration::from_secs(5))
+            .await
+            .map_err(|(e, _)| anyhow!("Kafka send to {} failed: {}", self.topic, e))?;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "kafka"
+    }
+}
+
+/// Placeholder when the `kafka` feature is disabled: construction fails with a
+/// clear message so configuring a Kafka sink without the feature is not silent.
+#[cfg(not(feature = "kafka"))]
+pub struct KafkaSink;
+
+#[cfg(not(feature = "kafka"))]
+impl KafkaSink {
+    pub fn new(_brokers: String, _topic: String) -> Result<Self> {
+        Err(ramparts_common::anyhow::anyhow!(
+            "Kafka audit sink requires the 'kafka' feature to be enabled"
+        ))
+    }
+}
+
+#[cfg(not(feature = "kafka"))]
+#[async_trait]
+impl AuditSink for KafkaSink {
+    async fn publish(&self, _event: &AuditEvent) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "kafka"
+    }
+}
@@ -0,0 +1,129 @@
+//! Asynchronous audit-event streaming of validation decisions to external
+//! sinks (HTTP webhook, Kafka) for SIEM/compliance.
+//!
+//! Events are serialized from [`ValidationResult`](crate::ValidationResult) and
+//! handed to a bounded channel; a background task drains the channel and
+//! dispatches to the configured sinks, so a slow or unavailable sink can never
+//! block the request path — it only causes events to be dropped (with a warning)
+//! once the buffer fills.
+
+mod kafka;
+mod webhook;
+
+pub use kafka::KafkaSink;
+pub use webhook::WebhookSink;
+
+use async_trait::async_trait;
+use ramparts_common::{
+    anyhow::Result,
+    tracing::{debug, warn},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// A single structured audit record for one validation decision.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub request_id: String,
+    pub timestamp: String,
+    pub method: String,
+    pub allowed: bool,
+    /// The rule or reason that produced the decision, when known.
+    pub matched_rule: Option<String>,
+    pub confidence: Option<f64>,
+    /// Redacted copy of the offending params (secrets stripped).
+    pub params: Value,
+}
+
+/// A destination audit events are published to. Implementations must be
+/// cancellation-safe and should not block indefinitely.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn publish(&self, event: &AuditEvent) -> Result<()>;
+
+    /// Short name for logging/diagnostics.
+    fn name(&self) -> &str;
+}
+
+/// Audit configuration carried on `ProxyConfig`.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct AuditConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Bounded channel capacity; events beyond this are dropped under load.
+    #[serde(default = "default_buffer")]
+    pub buffer_size: usize,
+    #[serde(default)]
+    pub sinks: Vec<AuditSinkConfig>,
+}
+
+fn default_buffer() -> usize {
+    1024
+}
+
+/// Declarative sink configuration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AuditSinkConfig {
+    Webhook { url: String },
+    Kafka { brokers: String, topic: String },
+}
+
+/// Owns the bounded channel and background dispatch task.
+pub struct AuditLogger {
+    tx: mpsc::Sender<AuditEvent>,
+}
+
+impl AuditLogger {
+    /// Build a logger from config, spawning the dispatch task. Returns `None`
+    /// when auditing is disabled or no sinks are configured.
+    pub fn from_config(config: &AuditConfig) -> Option<Arc<Self>> {
+        if !config.enabled || config.sinks.is_empty() {
+            return None;
+        }
+
+        let mut sinks: Vec<Arc<dyn AuditSink>> = Vec::new();
+        for sink in &config.sinks {
+            match build_sink(sink) {
+                Ok(s) => sinks.push(s),
+                Err(e) => warn!("Skipping unconfigurable audit sink: {}", e),
+            }
+        }
+        if sinks.is_empty() {
+            return None;
+        }
+
+        let (tx, mut rx) = mpsc::channel::<AuditEvent>(config.buffer_size);
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                for sink in &sinks {
+                    if let Err(e) = sink.publish(&event).await {
+                        warn!("Audit sink '{}' failed to publish: {}", sink.name(), e);
+                    }
+                }
+            }
+            debug!("Audit dispatch task stopped");
+        });
+
+        Some(Arc::new(Self { tx }))
+    }
+
+    /// Enqueue an event without blocking the caller. Drops (and warns) if the
+    /// buffer is full so the request path is never stalled by a slow sink.
+    pub fn emit(&self, event: AuditEvent) {
+        if let Err(e) = self.tx.try_send(event) {
+            warn!("Dropping audit event: {}", e);
+        }
+    }
+}
+
+fn build_sink(config: &AuditSinkConfig) -> Result<Arc<dyn AuditSink>> {
+    match config {
+        AuditSinkConfig::Webhook { url } => Ok(Arc::new(WebhookSink::new(url.clone())?)),
+        AuditSinkConfig::Kafka { brokers, topic } => {
+            Ok(Arc::new(KafkaSink::new(brokers.clone(), topic.clone())?))
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use super::{AuditEvent, AuditSink};
+use async_trait::async_trait;
+use ramparts_common::anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// Publishes audit events as JSON POST bodies to a generic HTTP endpoint.
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| anyhow!("failed to build webhook client: {}", e))?;
+        Ok(Self { url, client })
+    }
+}
+
+#[async_trait]
+impl AuditSink for WebhookSink {
+    async fn publish(&self, event: &AuditEvent) -> Result<()> {
+        let resp = self
+            .client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await
+            .map_err(|e| anyhow!("webhook POST to {} failed: {}", self.url, e))?;
+        if !resp.status().is_success() {
+            return Err(anyhow!(
+                "webhook {} returned status {}",
+                self.url,
+                resp.status()
+            ));
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "webhook"
+    }
+}
@@ -2,8 +2,11 @@ use ramparts_common::{
     anyhow::Result,
     tracing::{debug, error, info, warn},
 };
-use ramparts_proxy::{JavelinClient, ProxyConfig, ValidationService};
-use serde_json::{json, Value};
+use ramparts_proxy::{
+    stream::FrameDecision, JavelinClient, ManagerConfig, MultiTargetManager, ProxyConfig,
+    TargetSpec, ValidationService,
+};
+use serde_json::Value;
 use std::{env, process::Stdio, sync::Arc};
 use tokio::{
     io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter},
@@ -34,6 +37,13 @@ async fn main() -> Result<()> {
         env!("CARGO_PKG_VERSION")
     );
 
+    // When RAMPARTS_TARGETS holds a JSON array of target specs, run the
+    // multi-target manager: one guarded front door multiplexing several MCP
+    // servers, rather than the single-target stdio pipe below.
+    if let Some(targets) = env::var("RAMPARTS_TARGETS").ok().filter(|s| !s.is_empty()) {
+        return run_manager(&targets).await;
+    }
+
     // Get target command from environment
     let target_cmd = env::var("RAMPARTS_TARGET_CMD").map_err(|_| {
         ramparts_common::anyhow::anyhow!("RAMPARTS_TARGET_CMD environment variable required")
@@ -69,10 +79,39 @@ async fn main() -> Result<()> {
     ));
     let validation_service = Arc::new(ValidationService::new(javelin_client, config));
 
-    // Spawn target MCP server
-    let mut child = spawn_target_server(&target_cmd, &target_args).await?;
+    // When RAMPARTS_LISTEN_IPC is set, listen on a local IPC endpoint (Unix
+    // domain socket / Windows named pipe) and guard many concurrent clients;
+    // otherwise run the classic one-shot stdio pipe.
+    if let Some(endpoint) = env::var("RAMPARTS_LISTEN_IPC").ok().filter(|s| !s.is_empty()) {
+        return ipc::run_listener(validation_service, &endpoint, target_cmd, target_args).await;
+    }
+
+    run_connection(
+        validation_service,
+        tokio::io::stdin(),
+        tokio::io::stdout(),
+        &target_cmd,
+        &target_args,
+    )
+    .await
+}
+
+/// Spawn a target server and proxy one client connection through it in both
+/// directions. Shared by the stdio entry point and by each accepted IPC
+/// connection so the framing and validation pipeline is identical everywhere.
+async fn run_connection<R, W>(
+    validation_service: Arc<ValidationService>,
+    client_reader: R,
+    client_writer: W,
+    target_cmd: &str,
+    target_args: &[String],
+) -> Result<()>
+where
+    R: AsyncReadExt + Unpin + Send + 'static,
+    W: AsyncWriteExt + Unpin + Send + 'static,
+{
+    let mut child = spawn_target_server(target_cmd, target_args).await?;
 
-    // Get handles to child's stdin/stdout
     let child_stdin = child
         .stdin
         .take()
@@ -82,38 +121,36 @@ async fn main() -> Result<()> {
         .take()
         .ok_or_else(|| ramparts_common::anyhow::anyhow!("Failed to get child stdout"))?;
 
-    // Create shared state for request tracking
+    // Per-connection request tracking and a shared client writer (both proxy
+    // directions may write to the client: responses and inline error replies).
     let request_tracker = Arc::new(Mutex::new(std::collections::HashMap::<Value, Value>::new()));
+    let client_writer = Arc::new(Mutex::new(client_writer));
 
-    // Create bidirectional proxy tasks
-    let validation_service_clone = validation_service.clone();
-    let request_tracker_clone = request_tracker.clone();
-
-    // Task 1: Client stdin -> Child stdin (with request validation)
+    let service_c2s = validation_service.clone();
+    let tracker_c2s = request_tracker.clone();
+    let writer_c2s = client_writer.clone();
     let stdin_task = tokio::spawn(async move {
-        proxy_client_to_server(validation_service_clone, request_tracker_clone, child_stdin).await
+        proxy_client_to_server(service_c2s, tracker_c2s, client_reader, writer_c2s, child_stdin).await
     });
 
-    // Task 2: Child stdout -> Client stdout (with response validation)
     let stdout_task = tokio::spawn(async move {
-        proxy_server_to_client(validation_service, request_tracker, child_stdout).await
+        proxy_server_to_client(validation_service, request_tracker, child_stdout, client_writer)
+            .await
     });
 
-    // Wait for either task to complete (or fail)
     tokio::select! {
         result = stdin_task => {
             if let Err(e) = result? {
-                error!("Stdin proxy task failed: {}", e);
+                error!("Request proxy task failed: {}", e);
             }
         }
         result = stdout_task => {
             if let Err(e) = result? {
-                error!("Stdout proxy task failed: {}", e);
+                error!("Response proxy task failed: {}", e);
             }
         }
     }
 
-    // Clean up child process
     if let Err(e) = child.kill().await {
         warn!("Failed to kill child process: {}", e);
     }
@@ -121,20 +158,97 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Parse `RAMPARTS_TARGETS`, start the [`MultiTargetManager`], and serve client
+/// requests arriving on stdin over Content-Length framing, writing each guarded
+/// response back on stdout. This is the single front door in front of several
+/// MCP servers.
+async fn run_manager(targets_json: &str) -> Result<()> {
+    let targets: Vec<TargetSpec> = serde_json::from_str(targets_json).map_err(|e| {
+        ramparts_common::anyhow::anyhow!("Invalid RAMPARTS_TARGETS JSON: {}", e)
+    })?;
+    if targets.is_empty() {
+        return Err(ramparts_common::anyhow::anyhow!(
+            "RAMPARTS_TARGETS must list at least one target"
+        ));
+    }
+
+    let config = ProxyConfig::from_env()?;
+    let javelin_client = Arc::new(JavelinClient::with_behavior(
+        config.javelin.api_key.clone(),
+        config.javelin.base_url.clone(),
+        config.javelin.timeout_seconds,
+        &config.behavior,
+    ));
+    let validation_service = Arc::new(ValidationService::new(javelin_client, config));
+
+    let manager_config = ManagerConfig {
+        targets,
+        ..ManagerConfig::default()
+    };
+    let manager = MultiTargetManager::start(manager_config, validation_service).await?;
+
+    let mut reader = BufReader::new(tokio::io::stdin());
+    let writer = Arc::new(Mutex::new(BufWriter::new(tokio::io::stdout())));
+    loop {
+        match read_jsonrpc_message(&mut reader).await {
+            ReadResult::Message(raw) => {
+                let request: Value = match serde_json::from_str(&raw) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        warn!("Discarding malformed request: {}", e);
+                        continue;
+                    }
+                };
+                // A single failed request must not tear down the front door;
+                // log it and keep serving. Notifications return no response.
+                match manager.handle_request(&request).await {
+                    Ok(Some(response)) => {
+                        write_to_client(&writer, &serde_json::to_string(&response)?).await?;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Request handling failed: {}", e),
+                }
+            }
+            ReadResult::TooLarge(len) => {
+                write_to_client(&writer, &oversize_error_frame(len)).await?;
+            }
+            ReadResult::Eof => break,
+            ReadResult::Error(e) => {
+                error!("Failed to read request: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Proxy requests from client to server with validation
-async fn proxy_client_to_server(
+async fn proxy_client_to_server<R, W>(
     validation_service: Arc<ValidationService>,
     request_tracker: Arc<Mutex<std::collections::HashMap<Value, Value>>>,
+    client_reader: R,
+    client_writer: Arc<Mutex<W>>,
     mut child_stdin: tokio::process::ChildStdin,
-) -> Result<()> {
-    let stdin = tokio::io::stdin();
-    let mut reader = BufReader::new(stdin);
+) -> Result<()>
+where
+    R: AsyncReadExt + Unpin,
+    W: AsyncWriteExt + Unpin,
+{
+    let mut reader = BufReader::new(client_reader);
     let mut writer = BufWriter::new(&mut child_stdin);
+    // The Ramparts-side API key for this connection, checked before guardrails.
+    let api_key = env::var("RAMPARTS_API_KEY").ok();
 
     loop {
         match read_jsonrpc_message(&mut reader).await {
-            None => break, // EOF
-            Some(Ok(payload)) => {
+            ReadResult::Eof => break, // EOF
+            ReadResult::TooLarge(len) => {
+                warn!("Rejecting oversized request ({} bytes)", len);
+                ramparts_proxy::proxy_metrics::proxy_metrics().inc_blocked();
+                write_to_client(&client_writer, &oversize_error_frame(len)).await?;
+                continue;
+            }
+            ReadResult::Message(payload) => {
                 // Log redacted request preview
                 if let Ok(json) = serde_json::from_str::<Value>(&payload) {
                     let redacted = ramparts_proxy::logging::sanitize_json_for_log(&json);
@@ -150,12 +264,36 @@ async fn proxy_client_to_server(
                 // Parse JSON-RPC request
                 match serde_json::from_str::<Value>(&payload) {
                     Ok(request) => {
+                        let metrics = ramparts_proxy::proxy_metrics::proxy_metrics();
+                        let method = request
+                            .get("method")
+                            .and_then(Value::as_str)
+                            .unwrap_or("other")
+                            .to_string();
+
+                        // Enforce scoped API keys before consulting guardrails.
+                        if let Some(denied) = validation_service
+                            .authorize_key(api_key.as_deref(), &request)
+                        {
+                            warn!("Request rejected by key policy: {:?}", denied.reason);
+                            metrics.inc_blocked();
+                            let error_response =
+                                validation_service.create_blocked_response(&request, &denied);
+                            let out = serde_json::to_string(&error_response)?;
+                            write_to_client(&client_writer, &out).await?;
+                            continue;
+                        }
+
                         // Validate request
-                        match validation_service.validate_request(&request).await {
+                        let started = std::time::Instant::now();
+                        let validation = validation_service.validate_request(&request).await;
+                        metrics.observe_latency(&method, started.elapsed().as_secs_f64());
+                        match validation {
                             Ok(validation_result) => {
                                 if validation_result.allowed {
                                     // Request approved - forward to child
                                     debug!("Request approved, forwarding to target server");
+                                    metrics.inc_forwarded();
 
                                     // Track request for response correlation
                                     if let Some(id) = request.get("id") {
@@ -171,26 +309,22 @@ async fn proxy_client_to_server(
                                         "Request blocked by validation service: {:?}",
                                         validation_result.reason
                                     );
-                                    let error_response = json!({
-                                        "jsonrpc": "2.0",
-                                        "id": request.get("id"),
-                                        "error": {
-                                            "code": -32603,
-                                            "message": "Request blocked by Ramparts security",
-                                            "data": {
-                                                "reason": validation_result.reason,
-                                                "blocked_by": "ramparts-mcp-proxy-stdio"
-                                            }
-                                        }
-                                    });
+                                    metrics.inc_blocked();
+                                    // Carries the result's category, so a fail-closed
+                                    // failure surfaces as retryable `validation_unavailable`
+                                    // rather than a non-retryable `policy_denied`.
+                                    let error_response = validation_service
+                                        .create_blocked_response(&request, &validation_result);
                                     let out = serde_json::to_string(&error_response)?;
-                                    write_jsonrpc_message(&mut tokio::io::stdout(), &out).await?;
+                                    write_to_client(&client_writer, &out).await?;
                                 }
                             }
                             Err(e) => {
                                 warn!("Validation error: {}", e);
+                                metrics.inc_validation_error();
                                 if validation_service.is_fail_open() {
                                     // Forward request on validation error (fail-open policy)
+                                    metrics.inc_fail_open();
                                     write_jsonrpc_message(&mut writer, &payload).await?;
                                     writer.flush().await?;
                                 } else {
@@ -198,7 +332,7 @@ async fn proxy_client_to_server(
                                     let error_response = validation_service
                                         .create_error_response(&request, &e.to_string());
                                     let out = serde_json::to_string(&error_response)?;
-                                    write_jsonrpc_message(&mut tokio::io::stdout(), &out).await?;
+                                    write_to_client(&client_writer, &out).await?;
                                 }
                             }
                         }
@@ -211,7 +345,7 @@ async fn proxy_client_to_server(
                     }
                 }
             }
-            Some(Err(e)) => {
+            ReadResult::Error(e) => {
                 error!("Failed to read from stdin: {}", e);
                 break;
             }
@@ -222,77 +356,73 @@ async fn proxy_client_to_server(
 }
 
 /// Proxy responses from server to client with validation
-async fn proxy_server_to_client(
+async fn proxy_server_to_client<W>(
     validation_service: Arc<ValidationService>,
     request_tracker: Arc<Mutex<std::collections::HashMap<Value, Value>>>,
     child_stdout: tokio::process::ChildStdout,
-) -> Result<()> {
+    client_writer: Arc<Mutex<W>>,
+) -> Result<()>
+where
+    W: AsyncWriteExt + Unpin,
+{
     let mut reader = BufReader::new(child_stdout);
+    // Validate reassembled frames incrementally so a blocked frame is cut off
+    // as soon as it is seen rather than after the whole body is collected.
+    let mut validator = validation_service.stream_validator();
     loop {
         match read_jsonrpc_message(&mut reader).await {
-            None => break,
-            Some(Ok(payload)) => {
+            ReadResult::Eof => break,
+            ReadResult::TooLarge(len) => {
+                warn!("Dropping oversized response ({} bytes) from target server", len);
+                ramparts_proxy::proxy_metrics::proxy_metrics().inc_response_blocked();
+                write_to_client(&client_writer, &oversize_error_frame(len)).await?;
+                continue;
+            }
+            ReadResult::Message(payload) => {
                 // Log truncated response preview to avoid leaking sensitive content
                 debug!(
                     "Received response (preview): {}",
                     ramparts_proxy::logging::truncate_for_log(&payload)
                 );
 
-                // Parse JSON-RPC response
-                match serde_json::from_str::<Value>(&payload) {
-                    Ok(response) => {
-                        // Get original request context if available
-                        let _original_request = if let Some(id) = response.get("id") {
-                            let mut tracker = request_tracker.lock().await;
-                            tracker.remove(id)
-                        } else {
-                            None
-                        };
-
-                        // Validate response (optional for MVP)
-                        match validation_service.validate_response(&response).await {
-                            Ok(validation_result) => {
-                                if validation_result.allowed {
-                                    // Response approved - forward to client
-                                    debug!("Response approved, forwarding to client");
-                                    write_jsonrpc_message(&mut tokio::io::stdout(), &payload).await?;
-                                } else {
-                                    // Response blocked
-                                    warn!(
-                                        "Response blocked by validation service: {:?}",
-                                        validation_result.reason
-                                    );
-                                    let error_response = json!({
-                                        "jsonrpc": "2.0",
-                                        "id": response.get("id"),
-                                        "error": {
-                                            "code": -32603,
-                                            "message": "Response blocked by Ramparts security",
-                                            "data": {
-                                                "reason": validation_result.reason,
-                                                "blocked_by": "ramparts-mcp-proxy-stdio"
-                                            }
-                                        }
-                                    });
-                                    let out = serde_json::to_string(&error_response)?;
-                                    write_jsonrpc_message(&mut tokio::io::stdout(), &out).await?;
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Response validation failed: {}", e);
-                                // Forward response on validation error (fail-open for responses)
-                                write_jsonrpc_message(&mut tokio::io::stdout(), &payload).await?;
-                            }
-                        }
+                // Drop the correlated request context now the response is in.
+                if let Ok(response) = serde_json::from_str::<Value>(&payload) {
+                    if let Some(id) = response.get("id") {
+                        request_tracker.lock().await.remove(id);
                     }
+                }
+
+                // Each Content-Length framed response is already a complete
+                // message; validate it as one unit so a pretty-printed body is
+                // not re-split on its internal newlines.
+                let decision = match validator.push_message(&payload).await {
+                    Ok(decision) => decision,
                     Err(e) => {
-                        warn!("Failed to parse JSON-RPC response: {}", e);
-                        // Forward malformed responses as-is
-                        write_jsonrpc_message(&mut tokio::io::stdout(), &payload).await?;
+                        warn!("Response validation failed: {}", e);
+                        // Forward response on validation error (fail-open for responses)
+                        write_to_client(&client_writer, &payload).await?;
+                        continue;
+                    }
+                };
+
+                match decision {
+                    FrameDecision::Pass(frame) => {
+                        debug!("Response approved, forwarding to client");
+                        ramparts_proxy::proxy_metrics::proxy_metrics()
+                            .inc_response_forwarded();
+                        write_to_client(&client_writer, &frame).await?;
+                    }
+                    FrameDecision::Blocked(error_response) => {
+                        warn!("Streamed response frame blocked, tearing down stream");
+                        ramparts_proxy::proxy_metrics::proxy_metrics()
+                            .inc_response_blocked();
+                        let out = serde_json::to_string(&error_response)?;
+                        write_to_client(&client_writer, &out).await?;
+                        break;
                     }
                 }
             }
-            Some(Err(e)) => {
+            ReadResult::Error(e) => {
                 error!("Failed to read from child stdout: {}", e);
                 break;
             }
@@ -302,17 +432,46 @@ async fn proxy_server_to_client(
     Ok(())
 }
 
-/// Read a single JSON-RPC message supporting Content-Length framing and newline JSON fallback.
-async fn read_jsonrpc_message<R: AsyncReadExt + Unpin>(
-    reader: &mut BufReader<R>,
-) -> Option<Result<String, ramparts_common::anyhow::Error>> {
+/// Chunk size for streaming body reads so a large (but legal) body applies
+/// backpressure instead of forcing one giant allocation.
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Maximum message size accepted on both read and write paths. Configurable via
+/// `RAMPARTS_MAX_MESSAGE_BYTES`; defaults to 10 MiB.
+fn max_message_bytes() -> usize {
+    static MAX: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+    *MAX.get_or_init(|| {
+        env::var("RAMPARTS_MAX_MESSAGE_BYTES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(10 * 1024 * 1024)
+    })
+}
+
+/// Outcome of reading one framed message.
+enum ReadResult {
+    /// A complete message payload.
+    Message(String),
+    /// A message whose declared `Content-Length` exceeded the configured cap;
+    /// the body has been drained so the stream stays aligned.
+    TooLarge(usize),
+    /// End of stream.
+    Eof,
+    /// A transport error.
+    Error(ramparts_common::anyhow::Error),
+}
+
+/// Read a single JSON-RPC message supporting Content-Length framing and newline
+/// JSON fallback, bounded by [`max_message_bytes`] and read in fixed-size chunks.
+async fn read_jsonrpc_message<R: AsyncReadExt + Unpin>(reader: &mut BufReader<R>) -> ReadResult {
+    let max = max_message_bytes();
     let mut header = String::new();
     let mut content_length: Option<usize> = None;
 
     loop {
         header.clear();
         match reader.read_line(&mut header).await {
-            Ok(0) => return None,
+            Ok(0) => return ReadResult::Eof,
             Ok(_) => {
                 let line = header.trim_end();
                 if line.is_empty() {
@@ -323,41 +482,114 @@ async fn read_jsonrpc_message<R: AsyncReadExt + Unpin>(
                 }
             }
             Err(e) => {
-                return Some(Err(ramparts_common::anyhow::anyhow!(
+                return ReadResult::Error(ramparts_common::anyhow::anyhow!(
                     "read header failed: {}",
                     e
-                )));
+                ));
             }
         }
     }
 
     if let Some(len) = content_length {
-        let mut buf = vec![0u8; len];
-        if let Err(e) = reader.read_exact(&mut buf).await {
-            return Some(Err(ramparts_common::anyhow::anyhow!(
-                "read body failed: {}",
-                e
-            )));
+        if len > max {
+            // Drain the oversized body in chunks so the stream stays framed,
+            // then signal the caller to emit a framed rejection.
+            if let Err(e) = drain_exact(reader, len).await {
+                return ReadResult::Error(e);
+            }
+            return ReadResult::TooLarge(len);
+        }
+        // Read the body in bounded chunks rather than one `vec![0u8; len]`.
+        let mut buf = Vec::with_capacity(len.min(READ_CHUNK));
+        let mut remaining = len;
+        let mut chunk = vec![0u8; READ_CHUNK.min(len).max(1)];
+        while remaining > 0 {
+            let want = remaining.min(chunk.len());
+            match reader.read_exact(&mut chunk[..want]).await {
+                Ok(_) => {
+                    buf.extend_from_slice(&chunk[..want]);
+                    remaining -= want;
+                }
+                Err(e) => {
+                    return ReadResult::Error(ramparts_common::anyhow::anyhow!(
+                        "read body failed: {}",
+                        e
+                    ));
+                }
+            }
         }
-        let payload = String::from_utf8_lossy(&buf).to_string();
-        Some(Ok(payload))
+        ReadResult::Message(String::from_utf8_lossy(&buf).to_string())
     } else {
-        // Fallback: read a single JSON line
+        // Fallback: read a single JSON line, bounded to the same cap.
         let mut line = String::new();
         match reader.read_line(&mut line).await {
-            Ok(0) => None,
-            Ok(_) => Some(Ok(line.trim().to_string())),
-            Err(e) => Some(Err(ramparts_common::anyhow::anyhow!(
+            Ok(0) => ReadResult::Eof,
+            Ok(_) if line.len() > max => ReadResult::TooLarge(line.len()),
+            Ok(_) => ReadResult::Message(line.trim().to_string()),
+            Err(e) => ReadResult::Error(ramparts_common::anyhow::anyhow!(
                 "read line failed: {}",
                 e
-            ))),
+            )),
         }
     }
 }
 
+/// Discard exactly `len` bytes from the reader in fixed-size chunks.
+async fn drain_exact<R: AsyncReadExt + Unpin>(
+    reader: &mut BufReader<R>,
+    len: usize,
+) -> Result<()> {
+    let mut remaining = len;
+    let mut chunk = vec![0u8; READ_CHUNK];
+    while remaining > 0 {
+        let want = remaining.min(chunk.len());
+        reader.read_exact(&mut chunk[..want]).await?;
+        remaining -= want;
+    }
+    Ok(())
+}
+
+/// Build a framed `-32600` error for a message that exceeded the size cap.
+fn oversize_error_frame(len: usize) -> String {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": Value::Null,
+        "error": {
+            "code": -32600,
+            "message": "Message exceeds maximum allowed size",
+            "data": {
+                "content_length": len,
+                "max_bytes": max_message_bytes(),
+                "blocked_by": "ramparts-mcp-proxy-stdio"
+            }
+        }
+    });
+    body.to_string()
+}
+
+/// Write a framed JSON-RPC message to the shared client writer, flushing so the
+/// client sees it immediately. Both proxy directions share the writer, hence
+/// the mutex.
+async fn write_to_client<W>(client_writer: &Arc<Mutex<W>>, payload: &str) -> Result<()>
+where
+    W: AsyncWriteExt + Unpin,
+{
+    let mut guard = client_writer.lock().await;
+    write_jsonrpc_message(&mut *guard, payload).await?;
+    guard.flush().await?;
+    Ok(())
+}
+
 /// Write a JSON-RPC message using Content-Length framing.
 async fn write_jsonrpc_message<W: AsyncWriteExt + Unpin>(writer: &mut W, payload: &str) -> Result<()> {
     let bytes = payload.as_bytes();
+    if bytes.len() > max_message_bytes() {
+        return Err(ramparts_common::anyhow::anyhow!(
+            "refusing to write {}-byte message exceeding {}-byte cap",
+            bytes.len(),
+            max_message_bytes()
+        ));
+    }
     let header = format!("Content-Length: {}\r\n\r\n", bytes.len());
     writer.write_all(header.as_bytes()).await?;
     writer.write_all(bytes).await?;
@@ -419,3 +651,124 @@ async fn self_check() -> Result<()> {
 
     Ok(())
 }
+
+/// Local IPC transport: a single guarded MCP endpoint that co-located agents
+/// connect to, instead of the per-launch stdio fan-out. Uses a Unix domain
+/// socket on unix and a named pipe on Windows, reusing the same per-connection
+/// framing and validation pipeline as the stdio path.
+mod ipc {
+    use super::{run_connection, ValidationService};
+    use ramparts_common::{
+        anyhow::{anyhow, Result},
+        tracing::{error, info},
+    };
+    use std::sync::Arc;
+
+    /// Default endpoint names used when `RAMPARTS_LISTEN_IPC` is a truthy flag
+    /// rather than an explicit path.
+    #[cfg(unix)]
+    const DEFAULT_ENDPOINT: &str = "/tmp/ramparts.sock";
+    #[cfg(windows)]
+    const DEFAULT_ENDPOINT: &str = r"\\.\pipe\ramparts";
+
+    fn resolve_endpoint(endpoint: &str) -> String {
+        match endpoint {
+            "1" | "true" | "on" => DEFAULT_ENDPOINT.to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    pub async fn run_listener(
+        service: Arc<ValidationService>,
+        endpoint: &str,
+        target_cmd: String,
+        target_args: Vec<String>,
+    ) -> Result<()> {
+        let endpoint = resolve_endpoint(endpoint);
+        #[cfg(unix)]
+        {
+            run_unix(service, &endpoint, target_cmd, target_args).await
+        }
+        #[cfg(windows)]
+        {
+            run_windows(service, &endpoint, target_cmd, target_args).await
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = (service, endpoint, target_cmd, target_args);
+            Err(anyhow!("IPC transport is not supported on this platform"))
+        }
+    }
+
+    #[cfg(unix)]
+    async fn run_unix(
+        service: Arc<ValidationService>,
+        path: &str,
+        target_cmd: String,
+        target_args: Vec<String>,
+    ) -> Result<()> {
+        use tokio::net::UnixListener;
+
+        // Remove a stale socket from a previous run so bind() doesn't fail.
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path)
+            .map_err(|e| anyhow!("failed to bind IPC socket {}: {}", path, e))?;
+        info!("Ramparts listening for MCP clients on unix socket {}", path);
+
+        loop {
+            let (stream, _addr) = listener
+                .accept()
+                .await
+                .map_err(|e| anyhow!("IPC accept failed: {}", e))?;
+            let service = service.clone();
+            let cmd = target_cmd.clone();
+            let args = target_args.clone();
+            tokio::spawn(async move {
+                let (read_half, write_half) = tokio::io::split(stream);
+                if let Err(e) = run_connection(service, read_half, write_half, &cmd, &args).await {
+                    error!("IPC connection ended with error: {}", e);
+                }
+            });
+        }
+    }
+
+    #[cfg(windows)]
+    async fn run_windows(
+        service: Arc<ValidationService>,
+        pipe_name: &str,
+        target_cmd: String,
+        target_args: Vec<String>,
+    ) -> Result<()> {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        info!("Ramparts listening for MCP clients on named pipe {}", pipe_name);
+        // Keep a server instance pending so a new client can always connect.
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(pipe_name)
+            .map_err(|e| anyhow!("failed to create named pipe {}: {}", pipe_name, e))?;
+
+        loop {
+            server
+                .connect()
+                .await
+                .map_err(|e| anyhow!("named pipe connect failed: {}", e))?;
+            let connected = server;
+
+            // Stand up the next server instance before handling this client.
+            server = ServerOptions::new()
+                .create(pipe_name)
+                .map_err(|e| anyhow!("failed to create named pipe {}: {}", pipe_name, e))?;
+
+            let service = service.clone();
+            let cmd = target_cmd.clone();
+            let args = target_args.clone();
+            tokio::spawn(async move {
+                let (read_half, write_half) = tokio::io::split(connected);
+                if let Err(e) = run_connection(service, read_half, write_half, &cmd, &args).await {
+                    error!("IPC connection ended with error: {}", e);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Lenient JSONC parsing for hand-edited Ramparts config and MCP
+//! server-definition files.
+//!
+//! Strict `serde_json` rejects `//`/`/* */` comments and trailing commas, which
+//! forces users to strip the annotations they write to document their config.
+//! [`parse_jsonc`] accepts those as a superset of standard JSON: comments and
+//! trailing commas are blanked out *in place* — replaced by spaces of equal
+//! width, with newlines inside block comments preserved — so byte offsets are
+//! left untouched and `serde_json` still reports accurate line/column numbers on
+//! a genuine syntax error. Strict JSON parses unchanged.
+
+use ramparts_common::anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Parse a JSON document in lenient (JSONC) mode, tolerating comments and
+/// trailing commas. Returns the same value a strict parser would for input that
+/// is already valid JSON.
+pub fn parse_jsonc(input: &str) -> Result<Value> {
+    let cleaned = strip_trailing_commas(&strip_comments(input));
+    serde_json::from_str(&cleaned).map_err(|e| {
+        anyhow!(
+            "invalid JSON at line {}, column {}: {}",
+            e.line(),
+            e.column(),
+            e
+        )
+    })
+}
+
+/// Replace `//` line and `/* */` block comments with equal-width spaces,
+/// ignoring comment markers that appear inside string literals. Newlines are
+/// preserved so line numbers in later error messages stay correct.
+fn strip_comments(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            out.push(b);
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match b {
+            b'"' => {
+                in_string = true;
+                out.push(b);
+                i += 1;
+            }
+            b'/' if i + 1 < bytes.len() && bytes[i + 1] == b'/' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out.push(b' ');
+                    i += 1;
+                }
+            }
+            b'/' if i + 1 < bytes.len() && bytes[i + 1] == b'*' => {
+                out.push(b' ');
+                out.push(b' ');
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && i + 1 < bytes.len() && bytes[i + 1] == b'/') {
+                    // Keep newlines so line numbers are preserved.
+                    out.push(if bytes[i] == b'\n' { b'\n' } else { b' ' });
+                    i += 1;
+                }
+                if i < bytes.len() {
+                    out.push(b' ');
+                    out.push(b' ');
+                    i += 2;
+                }
+            }
+            _ => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    // Safe: a comment is blanked byte for byte up to an ASCII terminator, so a
+    // multi-byte UTF-8 character inside it is replaced whole, and string
+    // contents are copied verbatim; the output is always valid UTF-8.
+    String::from_utf8(out).unwrap_or_else(|_| input.to_string())
+}
+
+/// Replace a comma that follows a value and is immediately followed (ignoring
+/// whitespace) by a closing `}`/`]` with a space, so trailing commas parse. A
+/// comma with no value before it (e.g. `"a": ,}`) is kept so the syntax error
+/// is reported where it is. Commas inside string literals are left alone.
+fn strip_trailing_commas(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut in_string = false;
+    let mut escaped = false;
+    // Last non-whitespace byte seen outside string contents.
+    let mut prev: u8 = 0;
+
+    for i in 0..bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                prev = b'"';
+            }
+            continue;
+        }
+        let after_value = !matches!(prev, 0 | b',' | b':' | b'[' | b'{');
+        if !b.is_ascii_whitespace() {
+            prev = b;
+        }
+        match b {
+            b'"' => in_string = true,
+            b',' => {
+                let mut j = i + 1;
+                while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                if after_value && j < bytes.len() && (bytes[j] == b'}' || bytes[j] == b']') {
+                    out[i] = b' ';
+                }
+            }
+            _ => {}
+        }
+    }
+
+    String::from_utf8(out).unwrap_or_else(|_| input.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parses_strict_json_unchanged() {
+        let v = parse_jsonc(r#"{"a": 1, "b": ["x", "y"]}"#).unwrap();
+        assert_eq!(v, json!({"a": 1, "b": ["x", "y"]}));
+    }
+
+    #[test]
+    fn test_accepts_comments_and_trailing_commas() {
+        let text = r#"
+        {
+            // the upstream endpoint
+            "url": "https://example.test", /* inline */
+            "headers": {
+                "x-note": "a,b", // comma inside a string must survive
+            },
+            "targets": [
+                "one",
+                "two",
+            ],
+        }
+        "#;
+        let v = parse_jsonc(text).unwrap();
+        assert_eq!(v["url"], "https://example.test");
+        assert_eq!(v["headers"]["x-note"], "a,b");
+        assert_eq!(v["targets"], json!(["one", "two"]));
+    }
+
+    #[test]
+    fn test_reports_line_column_on_error() {
+        let err = parse_jsonc("{\n  \"a\": ,\n}").unwrap_err().to_string();
+        assert!(err.contains("line 2"), "{err}");
+    }
+}
@@ -0,0 +1,332 @@
+//! Scoped, expiring Ramparts-side API keys, independent of the upstream Javelin
+//! API key.
+//!
+//! Each key carries a validity window, a set of MCP methods/tools it may invoke,
+//! and an optional per-minute rate ceiling. The `/validate` handler and the
+//! stdio request path consult the store *before* calling the guardrails service,
+//! so expired or out-of-scope calls are rejected cheaply with a clear reason.
+//! Keys are stored hashed in a config file loaded at startup and hot-reloaded on
+//! change.
+
+use ramparts_common::{
+    anyhow::{anyhow, Context, Result},
+    tracing::{info, warn},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+
+/// A key record as stored on disk (the secret itself is never persisted — only
+/// its SHA-256 hex digest).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRecord {
+    /// Opaque identifier shown in management output.
+    pub id: String,
+    /// Hex-encoded SHA-256 of the key material.
+    pub hash: String,
+    /// RFC3339 instant before which the key is not yet valid.
+    pub not_before: Option<String>,
+    /// RFC3339 instant after which the key has expired.
+    pub not_after: Option<String>,
+    /// MCP methods / `tool:<name>` scopes this key may invoke. `*` allows all.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Optional ceiling on requests per minute.
+    pub rate_per_minute: Option<u32>,
+}
+
+/// Config file shape for the key store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyFile {
+    #[serde(default)]
+    pub keys: Vec<KeyRecord>,
+}
+
+/// Key-store configuration carried on `ProxyConfig`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeysConfig {
+    /// Path to a YAML/JSON file of hashed keys. When unset, key enforcement is
+    /// disabled (all-or-nothing upstream auth only).
+    pub file: Option<PathBuf>,
+}
+
+/// Outcome of consulting the store for a single call.
+#[derive(Debug, Clone)]
+pub enum KeyDecision {
+    /// Enforcement is off, or the key is valid and in scope.
+    Allow,
+    /// The call is rejected; carries a client-facing reason.
+    Deny(String),
+}
+
+/// Per-key fixed-window rate counter: the count resets once 60 seconds have
+/// passed since the window started.
+#[derive(Default)]
+struct RateState {
+    window_start: Option<Instant>,
+    count: u32,
+}
+
+/// Loads, validates, and hot-reloads the set of Ramparts API keys.
+pub struct KeyStore {
+    records: Arc<RwLock<HashMap<String, KeyRecord>>>,
+    rates: Mutex<HashMap<String, RateState>>,
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl KeyStore {
+    /// Build the store from config. Returns `None` when no file is configured,
+    /// meaning key enforcement is disabled.
+    pub fn from_config(config: &KeysConfig) -> Result<Option<Arc<Self>>> {
+        let Some(path) = config.file.clone() else {
+            return Ok(None);
+        };
+
+        let records = Arc::new(RwLock::new(index(load_file(&path)?)));
+
+        let slot = records.clone();
+        let watch_target = path.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_ok() {
+                match load_file(&watch_target) {
+                    Ok(file) => {
+                        if let Ok(mut guard) = slot.write() {
+                            *guard = index(file);
+                            info!("Reloaded {} API keys from {}", guard.len(), watch_target.display());
+                        }
+                    }
+                    Err(e) => warn!("Ignoring invalid key-file reload: {}", e),
+                }
+            }
+        })
+        .map_err(|e| anyhow!("failed to create key-file watcher: {}", e))?;
+
+        use notify::Watcher;
+        watcher
+            .watch(&path, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| anyhow!("failed to watch key file {}: {}", path.display(), e))?;
+
+        Ok(Some(Arc::new(Self {
+            records,
+            rates: Mutex::new(HashMap::new()),
+            _watcher: Some(watcher),
+        })))
+    }
+
+    /// Check a presented key against the store for the method/tool implied by
+    /// `request`, enforcing validity window, scope, and rate ceiling.
+    pub fn check(&self, api_key: Option<&str>, request: &Value) -> KeyDecision {
+        let Some(key) = api_key else {
+            return KeyDecision::Deny("missing Ramparts API key".to_string());
+        };
+        let digest = hash_key(key);
+
+        let record = match self.records.read().unwrap().get(&digest).cloned() {
+            Some(r) => r,
+            None => return KeyDecision::Deny("unknown Ramparts API key".to_string()),
+        };
+
+        let now = chrono::Utc::now();
+        if let Some(nb) = &record.not_before {
+            if parse_time(nb).map(|t| now < t).unwrap_or(false) {
+                return KeyDecision::Deny(format!("key '{}' is not yet valid", record.id));
+            }
+        }
+        if let Some(na) = &record.not_after {
+            if parse_time(na).map(|t| now > t).unwrap_or(false) {
+                return KeyDecision::Deny(format!("key '{}' has expired", record.id));
+            }
+        }
+
+        if !self.in_scope(&record, request) {
+            let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+            return KeyDecision::Deny(format!(
+                "key '{}' is not scoped for '{}'",
+                record.id, method
+            ));
+        }
+
+        if let Some(limit) = record.rate_per_minute {
+            if self.over_rate(&record.id, limit) {
+                return KeyDecision::Deny(format!(
+                    "key '{}' exceeded its rate ceiling of {}/min",
+                    record.id, limit
+                ));
+            }
+        }
+
+        KeyDecision::Allow
+    }
+
+    fn in_scope(&self, record: &KeyRecord, request: &Value) -> bool {
+        if record.scopes.iter().any(|s| s == "*") {
+            return true;
+        }
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+        if record.scopes.iter().any(|s| s == method) {
+            return true;
+        }
+        // For tools/call, allow `tool:<name>` scopes.
+        if method == "tools/call" {
+            if let Some(tool) = request.pointer("/params/name").and_then(Value::as_str) {
+                let needle = format!("tool:{}", tool);
+                return record.scopes.iter().any(|s| *s == needle);
+            }
+        }
+        false
+    }
+
+    fn over_rate(&self, id: &str, limit: u32) -> bool {
+        let mut rates = self.rates.lock().unwrap();
+        let state = rates.entry(id.to_string()).or_default();
+        let now = Instant::now();
+        match state.window_start {
+            Some(start) if now.duration_since(start).as_secs() < 60 => {
+                state.count += 1;
+            }
+            _ => {
+                state.window_start = Some(now);
+                state.count = 1;
+            }
+        }
+        state.count > limit
+    }
+
+    /// Snapshot of each key's status for the `/keys/status` endpoint.
+    pub fn status(&self) -> Value {
+        let now = chrono::Utc::now();
+        let keys: Vec<Value> = self
+            .records
+            .read()
+            .unwrap()
+            .values()
+            .map(|r| {
+                let expiring_soon = r
+                    .not_after
+                    .as_deref()
+                    .and_then(parse_time)
+                    .map(|t| t > now && (t - now) < chrono::Duration::hours(24))
+                    .unwrap_or(false);
+                let expired = r
+                    .not_after
+                    .as_deref()
+                    .and_then(parse_time)
+                    .map(|t| now > t)
+                    .unwrap_or(false);
+                json!({
+                    "id": r.id,
+                    "scopes": r.scopes,
+                    "not_before": r.not_before,
+                    "not_after": r.not_after,
+                    "rate_per_minute": r.rate_per_minute,
+                    "status": if expired { "expired" } else if expiring_soon { "expiring" } else { "active" },
+                })
+            })
+            .collect();
+        json!({ "keys": keys })
+    }
+}
+
+fn index(file: KeyFile) -> HashMap<String, KeyRecord> {
+    file.keys
+        .into_iter()
+        .map(|r| (r.hash.to_ascii_lowercase(), r))
+        .collect()
+}
+
+fn load_file(path: &PathBuf) -> Result<KeyFile> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("reading key file {}", path.display()))?;
+    let is_json = path.extension().map(|e| e == "json").unwrap_or(false);
+    if is_json {
+        // Lenient JSONC so operators can annotate their key manifests in place.
+        let value = crate::jsonc::parse_jsonc(&text)
+            .with_context(|| format!("parsing JSON key file {}", path.display()))?;
+        serde_json::from_value(value)
+            .with_context(|| format!("parsing JSON key file {}", path.display()))
+    } else {
+        serde_yaml::from_str(&text).with_context(|| format!("parsing YAML key file {}", path.display()))
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn parse_time(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|t| t.with_timezone(&chrono::Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with(records: Vec<KeyRecord>) -> KeyStore {
+        KeyStore {
+            records: Arc::new(RwLock::new(index(KeyFile { keys: records }))),
+            rates: Mutex::new(HashMap::new()),
+            _watcher: None,
+        }
+    }
+
+    fn record(scopes: &[&str]) -> KeyRecord {
+        KeyRecord {
+            id: "k1".into(),
+            hash: hash_key("secret"),
+            not_before: None,
+            not_after: None,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            rate_per_minute: None,
+        }
+    }
+
+    #[test]
+    fn test_unknown_and_missing_key() {
+        let store = store_with(vec![record(&["*"])]);
+        let req = json!({"method": "tools/list"});
+        assert!(matches!(store.check(None, &req), KeyDecision::Deny(_)));
+        assert!(matches!(store.check(Some("nope"), &req), KeyDecision::Deny(_)));
+        assert!(matches!(store.check(Some("secret"), &req), KeyDecision::Allow));
+    }
+
+    #[test]
+    fn test_scope_enforcement() {
+        let store = store_with(vec![record(&["tools/list"])]);
+        assert!(matches!(
+            store.check(Some("secret"), &json!({"method": "tools/list"})),
+            KeyDecision::Allow
+        ));
+        assert!(matches!(
+            store.check(Some("secret"), &json!({"method": "resources/read"})),
+            KeyDecision::Deny(_)
+        ));
+    }
+
+    #[test]
+    fn test_tool_scope() {
+        let store = store_with(vec![record(&["tool:search"])]);
+        let ok = json!({"method": "tools/call", "params": {"name": "search"}});
+        let no = json!({"method": "tools/call", "params": {"name": "delete"}});
+        assert!(matches!(store.check(Some("secret"), &ok), KeyDecision::Allow));
+        assert!(matches!(store.check(Some("secret"), &no), KeyDecision::Deny(_)));
+    }
+
+    #[test]
+    fn test_rate_ceiling() {
+        let mut rec = record(&["*"]);
+        rec.rate_per_minute = Some(2);
+        let store = store_with(vec![rec]);
+        let req = json!({"method": "tools/list"});
+        assert!(matches!(store.check(Some("secret"), &req), KeyDecision::Allow));
+        assert!(matches!(store.check(Some("secret"), &req), KeyDecision::Allow));
+        assert!(matches!(store.check(Some("secret"), &req), KeyDecision::Deny(_)));
+    }
+}
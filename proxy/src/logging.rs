@@ -1,4 +1,75 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
+use std::fmt;
+
+/// Placeholder printed in place of any redacted secret.
+pub const REDACTED: &str = "***REDACTED***";
+
+/// A string holding a credential (API key, bearer token, password) that must
+/// never surface in logs or error messages.
+///
+/// `Display` and `Debug` both print [`REDACTED`], so the value stays hidden even
+/// when it flows through a `Debug` derive, a `format!("{:?}", …)`, or an
+/// `anyhow` error chain. `Serialize`/`Deserialize` are transparent to the
+/// underlying string so config round-trips and outbound auth headers keep
+/// working; reach the plaintext only through [`SensitiveString::inner`].
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct SensitiveString(String);
+
+impl SensitiveString {
+    /// Wrap a credential.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Borrow the underlying secret. Use only where the plaintext is genuinely
+    /// required (e.g. building an outbound `Authorization` header).
+    pub fn inner(&self) -> &str {
+        &self.0
+    }
+
+    /// True when no credential is set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for SensitiveString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Debug for SensitiveString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for SensitiveString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SensitiveString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Serialize for SensitiveString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SensitiveString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
 
 /// Keys whose values should be redacted in logs (case-insensitive)
 const SENSITIVE_KEYS: &[&str] = &[
@@ -17,27 +88,369 @@ const SENSITIVE_KEYS: &[&str] = &[
     "set-cookie",
 ];
 
-/// Redact sensitive values and truncate long strings in a JSON value for logging.
+/// Recognizes a class of secret-shaped substrings and redacts it in place.
+///
+/// Matchers run over every `Value::String` before truncation, so a credential
+/// pasted into a free-form `description` or nested tool output is caught even
+/// when it does not sit under a [known key](is_sensitive_key). Register custom
+/// patterns by building your own `Vec<Box<dyn SecretMatcher>>` and passing it to
+/// [`sanitize_json_for_log_with`].
+pub trait SecretMatcher: Send + Sync {
+    /// Scan `input` and return a copy with every match replaced by a typed
+    /// marker, or `None` when the matcher found nothing (so surrounding prose is
+    /// left untouched).
+    fn redact(&self, input: &str) -> Option<String>;
+}
+
+/// The built-in matchers: JWTs, bearer/API-key prefixes, and PEM blocks.
+pub fn default_secret_matchers() -> Vec<Box<dyn SecretMatcher>> {
+    vec![
+        Box::new(JwtMatcher::new()),
+        Box::new(BearerMatcher::new()),
+        Box::new(PemMatcher::new()),
+    ]
+}
+
+/// Marker substituted for a subtree deeper than [`SanitizeOptions::max_depth`].
+const TRUNCATED_DEPTH: &str = "***TRUNCATED_DEPTH***";
+/// Marker substituted once the [`SanitizeOptions::max_nodes`] budget is spent.
+const TRUNCATED_NODES: &str = "***TRUNCATED_NODES***";
+
+/// Bounds on how much of a JSON value [`sanitize_json_for_log_detailed`] will
+/// traverse, protecting the logger from hostile MCP output.
+#[derive(Debug, Clone, Copy)]
+pub struct SanitizeOptions {
+    /// Subtrees deeper than this are collapsed to [`TRUNCATED_DEPTH`].
+    pub max_depth: usize,
+    /// Total number of values emitted before the rest is collapsed to
+    /// [`TRUNCATED_NODES`].
+    pub max_nodes: usize,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 64,
+            max_nodes: 100_000,
+        }
+    }
+}
+
+/// Result of a bounded sanitization pass.
+#[derive(Debug, Clone)]
+pub struct SanitizeReport {
+    /// The sanitized value.
+    pub value: Value,
+    /// Whether a subtree was dropped for exceeding `max_depth`.
+    pub depth_truncated: bool,
+    /// Whether emission stopped early for exceeding `max_nodes`.
+    pub node_truncated: bool,
+}
+
+/// Redact sensitive values and truncate long strings in a JSON value for
+/// logging, using the [default matchers](default_secret_matchers).
 pub fn sanitize_json_for_log(value: &Value) -> Value {
-    match value {
-        Value::Object(map) => {
-            let mut out = serde_json::Map::with_capacity(map.len());
-            for (k, v) in map {
-                let redacted = if is_sensitive_key(k) {
-                    Value::String("***REDACTED***".into())
-                } else {
-                    sanitize_json_for_log(v)
+    sanitize_json_for_log_with(value, &default_secret_matchers())
+}
+
+/// Like [`sanitize_json_for_log`] but with a caller-supplied matcher set, so
+/// deployments can register site-specific secret patterns.
+pub fn sanitize_json_for_log_with(value: &Value, matchers: &[Box<dyn SecretMatcher>]) -> Value {
+    sanitize_json_for_log_detailed(value, matchers, SanitizeOptions::default()).value
+}
+
+/// Depth- and node-bounded sanitization.
+///
+/// The traversal is iterative — an explicit work-stack of borrowed values plus
+/// an output-builder stack — so a maliciously deep payload from an untrusted MCP
+/// server cannot blow the native stack while we are merely logging it. Subtrees
+/// past `max_depth` collapse to [`TRUNCATED_DEPTH`] and, once the `max_nodes`
+/// budget is spent, every further value collapses to [`TRUNCATED_NODES`]; the
+/// returned [`SanitizeReport`] records whether either clip occurred.
+pub fn sanitize_json_for_log_detailed(
+    root: &Value,
+    matchers: &[Box<dyn SecretMatcher>],
+    opts: SanitizeOptions,
+) -> SanitizeReport {
+    /// An in-progress container on the output-builder stack.
+    enum Frame<'a> {
+        Object {
+            out: serde_json::Map<String, Value>,
+            iter: serde_json::map::Iter<'a>,
+            depth: usize,
+            key_in_parent: Option<String>,
+        },
+        Array {
+            out: Vec<Value>,
+            iter: std::slice::Iter<'a, Value>,
+            depth: usize,
+            key_in_parent: Option<String>,
+        },
+    }
+
+    // Attach a finished value to the container currently on top of the stack,
+    // or surface it as the root when the stack is empty.
+    fn deliver(stack: &mut Vec<Frame<'_>>, finished: &mut Option<Value>, value: Value, key: Option<String>) {
+        match stack.last_mut() {
+            Some(Frame::Object { out, .. }) => {
+                out.insert(key.unwrap_or_default(), value);
+            }
+            Some(Frame::Array { out, .. }) => out.push(value),
+            None => *finished = Some(value),
+        }
+    }
+
+    let mut report = SanitizeReport {
+        value: Value::Null,
+        depth_truncated: false,
+        node_truncated: false,
+    };
+    let mut nodes: usize = 0;
+    let mut finished: Option<Value> = None;
+    let mut stack: Vec<Frame> = Vec::new();
+
+    // Seed the traversal with the root.
+    match root {
+        Value::Object(map) => stack.push(Frame::Object {
+            out: serde_json::Map::with_capacity(map.len()),
+            iter: map.iter(),
+            depth: 0,
+            key_in_parent: None,
+        }),
+        Value::Array(arr) => stack.push(Frame::Array {
+            out: Vec::with_capacity(arr.len()),
+            iter: arr.iter(),
+            depth: 0,
+            key_in_parent: None,
+        }),
+        scalar => finished = Some(scalar_for_log(scalar, matchers)),
+    }
+
+    while !stack.is_empty() {
+        // Pull the next child of the container on top of the stack, ending the
+        // borrow before we mutate the stack below.
+        let next = match stack.last_mut().unwrap() {
+            Frame::Object { iter, depth, .. } => iter.next().map(|(k, v)| (Some(k.clone()), v, *depth)),
+            Frame::Array { iter, depth, .. } => iter.next().map(|v| (None, v, *depth)),
+        };
+
+        let (key, child, parent_depth) = match next {
+            Some(entry) => entry,
+            None => {
+                // The container is fully built; pop it and hand it to its parent.
+                let (value, key) = match stack.pop().unwrap() {
+                    Frame::Object { out, key_in_parent, .. } => (Value::Object(out), key_in_parent),
+                    Frame::Array { out, key_in_parent, .. } => (Value::Array(out), key_in_parent),
                 };
-                out.insert(k.clone(), redacted);
+                deliver(&mut stack, &mut finished, value, key);
+                continue;
+            }
+        };
+
+        // Node budget: once spent, collapse everything still pending.
+        if nodes >= opts.max_nodes {
+            report.node_truncated = true;
+            deliver(&mut stack, &mut finished, Value::String(TRUNCATED_NODES.into()), key);
+            continue;
+        }
+        nodes += 1;
+
+        // Key-name redaction takes precedence over the child's own shape.
+        if let Some(k) = &key {
+            if is_sensitive_key(k) {
+                deliver(&mut stack, &mut finished, Value::String(REDACTED.into()), key);
+                continue;
+            }
+        }
+
+        let child_depth = parent_depth + 1;
+        match child {
+            Value::Object(map) => {
+                if child_depth > opts.max_depth {
+                    report.depth_truncated = true;
+                    deliver(&mut stack, &mut finished, Value::String(TRUNCATED_DEPTH.into()), key);
+                } else {
+                    stack.push(Frame::Object {
+                        out: serde_json::Map::with_capacity(map.len()),
+                        iter: map.iter(),
+                        depth: child_depth,
+                        key_in_parent: key,
+                    });
+                }
+            }
+            Value::Array(arr) => {
+                if child_depth > opts.max_depth {
+                    report.depth_truncated = true;
+                    deliver(&mut stack, &mut finished, Value::String(TRUNCATED_DEPTH.into()), key);
+                } else {
+                    stack.push(Frame::Array {
+                        out: Vec::with_capacity(arr.len()),
+                        iter: arr.iter(),
+                        depth: child_depth,
+                        key_in_parent: key,
+                    });
+                }
             }
-            Value::Object(out)
+            scalar => deliver(&mut stack, &mut finished, scalar_for_log(scalar, matchers), key),
         }
-        Value::Array(arr) => Value::Array(arr.iter().map(sanitize_json_for_log).collect()),
-        Value::String(s) => Value::String(truncate_for_log(s)),
+    }
+
+    report.value = finished.unwrap_or(Value::Null);
+    report
+}
+
+/// Sanitize a single scalar (non-container) value for logging.
+fn scalar_for_log(value: &Value, matchers: &[Box<dyn SecretMatcher>]) -> Value {
+    match value {
+        // A string that already reads as the redaction marker (e.g. from an
+        // earlier pass) is left untouched so sanitization stays idempotent.
+        // `SensitiveString` serializes to its plaintext, so it is not caught here.
+        Value::String(s) if s == REDACTED => Value::String(REDACTED.into()),
+        Value::String(s) => Value::String(truncate_for_log(&scan_secrets(s, matchers))),
         other => other.clone(),
     }
 }
 
+/// Run every matcher over `input`, redacting matched substrings in place.
+fn scan_secrets(input: &str, matchers: &[Box<dyn SecretMatcher>]) -> String {
+    let mut current = input.to_string();
+    for m in matchers {
+        if let Some(redacted) = m.redact(&current) {
+            current = redacted;
+        }
+    }
+    current
+}
+
+/// Detects JSON Web Tokens by shape, then confirms by base64url-decoding the
+/// header and payload and checking both parse as JSON objects. The confirmation
+/// step avoids false positives on dotted version strings like `1.2.3`.
+struct JwtMatcher {
+    regex: Regex,
+}
+
+impl JwtMatcher {
+    fn new() -> Self {
+        // Three non-empty base64url segments.
+        let regex = Regex::new(r"[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+")
+            .expect("static JWT regex");
+        Self { regex }
+    }
+}
+
+impl SecretMatcher for JwtMatcher {
+    fn redact(&self, input: &str) -> Option<String> {
+        replace_matches(&self.regex, input, "***REDACTED_JWT***", |m| {
+            let mut parts = m.splitn(3, '.');
+            let header = parts.next().unwrap_or_default();
+            let payload = parts.next().unwrap_or_default();
+            looks_like_json_object(header) && looks_like_json_object(payload)
+        })
+    }
+}
+
+/// Detects `Bearer `/`sk-`/`ghp_`-prefixed tokens followed by a run of at least
+/// 20 token characters.
+struct BearerMatcher {
+    regex: Regex,
+}
+
+impl BearerMatcher {
+    fn new() -> Self {
+        let regex = Regex::new(r"(?:Bearer\s+|sk-|ghp_)[A-Za-z0-9_\-]{20,}")
+            .expect("static bearer regex");
+        Self { regex }
+    }
+}
+
+impl SecretMatcher for BearerMatcher {
+    fn redact(&self, input: &str) -> Option<String> {
+        replace_matches(&self.regex, input, "***REDACTED_BEARER***", |_| true)
+    }
+}
+
+/// Detects PEM-armored key/certificate blocks.
+struct PemMatcher {
+    regex: Regex,
+}
+
+impl PemMatcher {
+    fn new() -> Self {
+        let regex = Regex::new(r"(?s)-----BEGIN.*?-----END[^-]*-----")
+            .expect("static PEM regex");
+        Self { regex }
+    }
+}
+
+impl SecretMatcher for PemMatcher {
+    fn redact(&self, input: &str) -> Option<String> {
+        replace_matches(&self.regex, input, "***REDACTED_PEM***", |_| true)
+    }
+}
+
+/// Replace every `regex` match for which `confirm` holds with `marker`,
+/// preserving the surrounding text. Returns `None` when nothing matched.
+fn replace_matches(
+    regex: &Regex,
+    input: &str,
+    marker: &str,
+    confirm: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let mut out = String::new();
+    let mut last = 0;
+    let mut hit = false;
+    for m in regex.find_iter(input) {
+        if !confirm(m.as_str()) {
+            continue;
+        }
+        out.push_str(&input[last..m.start()]);
+        out.push_str(marker);
+        last = m.end();
+        hit = true;
+    }
+    if !hit {
+        return None;
+    }
+    out.push_str(&input[last..]);
+    Some(out)
+}
+
+/// True when `segment` base64url-decodes to bytes that parse as a JSON object.
+fn looks_like_json_object(segment: &str) -> bool {
+    let Some(bytes) = base64url_decode(segment) else {
+        return false;
+    };
+    serde_json::from_slice::<serde_json::Map<String, Value>>(&bytes).is_ok()
+}
+
+/// Minimal base64url (no-padding) decoder. Returns `None` on any invalid input.
+fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    fn val(c: u8) -> Option<u8> {
+        match c {
+            b'A'..=b'Z' => Some(c - b'A'),
+            b'a'..=b'z' => Some(c - b'a' + 26),
+            b'0'..=b'9' => Some(c - b'0' + 52),
+            b'-' => Some(62),
+            b'_' => Some(63),
+            _ => None,
+        }
+    }
+
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits = 0u32;
+    for &c in input.as_bytes() {
+        let v = val(c)? as u32;
+        acc = (acc << 6) | v;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
 /// Return a short, safe preview of a string for logs.
 pub fn truncate_for_log(input: &str) -> String {
     const MAX: usize = 128;
@@ -71,4 +484,68 @@ mod tests {
         assert_eq!(s["nested"]["x-api-key"], "***REDACTED***");
         assert!(s["nested"]["ok"].as_str().unwrap().len() <= 160);
     }
+
+    #[test]
+    fn test_sensitive_string_redacts_but_serializes() {
+        let secret = SensitiveString::new("sk-super-secret");
+        assert_eq!(format!("{secret}"), "***REDACTED***");
+        assert_eq!(format!("{secret:?}"), "***REDACTED***");
+        assert_eq!(secret.inner(), "sk-super-secret");
+        // Serialization stays transparent for outbound use / config round-trips.
+        assert_eq!(serde_json::to_value(&secret).unwrap(), json!("sk-super-secret"));
+        let back: SensitiveString = serde_json::from_value(json!("sk-super-secret")).unwrap();
+        assert_eq!(back, secret);
+    }
+
+    #[test]
+    fn test_redacts_secret_shaped_values_in_free_text() {
+        // header {"alg":"HS256"} . payload {"sub":"1"} . sig
+        let jwt = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.c2ln";
+        let v = json!({
+            "description": format!("run the tool, the jwt is {jwt} thanks"),
+            "version": "1.2.3",
+            "args": "token sk-abcdefghijklmnopqrstuvwxyz0123",
+        });
+        let s = sanitize_json_for_log(&v);
+        let desc = s["description"].as_str().unwrap();
+        assert!(desc.contains("***REDACTED_JWT***"), "{desc}");
+        assert!(desc.contains("run the tool"));
+        // Dotted version strings must not be mistaken for JWTs.
+        assert_eq!(s["version"], "1.2.3");
+        assert!(s["args"].as_str().unwrap().contains("***REDACTED_BEARER***"));
+    }
+
+    #[test]
+    fn test_depth_bounded_traversal_does_not_recurse() {
+        // Build a payload far deeper than any sane native stack would survive
+        // via recursion.
+        let mut v = json!("leaf");
+        for _ in 0..5000 {
+            v = json!({ "next": v });
+        }
+        let opts = SanitizeOptions {
+            max_depth: 8,
+            ..Default::default()
+        };
+        let report = sanitize_json_for_log_detailed(&v, &default_secret_matchers(), opts);
+        assert!(report.depth_truncated);
+        // The root is depth 0, so containers down to depth 8 are kept; walk one
+        // level past the cap and confirm the subtree was collapsed there.
+        let mut cur = &report.value;
+        for _ in 0..9 {
+            cur = &cur["next"];
+        }
+        assert_eq!(cur, &json!("***TRUNCATED_DEPTH***"));
+    }
+
+    #[test]
+    fn test_node_budget_clips_wide_payload() {
+        let wide: Vec<Value> = (0..100).map(|i| json!(i)).collect();
+        let opts = SanitizeOptions {
+            max_nodes: 10,
+            ..Default::default()
+        };
+        let report = sanitize_json_for_log_detailed(&json!(wide), &default_secret_matchers(), opts);
+        assert!(report.node_truncated);
+    }
 }
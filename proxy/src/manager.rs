@@ -0,0 +1,378 @@
+//! Multi-target manager that multiplexes several MCP servers behind one guarded
+//! front door.
+//!
+//! Where the stdio binary spawns exactly one target, the manager reads a config
+//! of named targets, spawns a child per target, and routes each JSON-RPC request
+//! to the correct child based on a server selector (an `X-Ramparts-Server`-style
+//! `server` field, or a `<server>/<method>` namespace prefix). It owns a map of
+//! children with per-child request trackers, restarts dead children, and
+//! aggregates `tools/list` / `resources/list` into one merged capability view.
+//! Every forwarded request and response still passes through [`ValidationService`].
+
+use ramparts_common::{
+    anyhow::{anyhow, Result},
+    tracing::{debug, error, info, warn},
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdout, Command};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+
+use crate::ValidationService;
+
+/// One named upstream MCP server.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TargetSpec {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Manager configuration carried on `ProxyConfig`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ManagerConfig {
+    #[serde(default)]
+    pub targets: Vec<TargetSpec>,
+    /// How often to check children for exit and restart them.
+    #[serde(default = "default_health_interval")]
+    pub health_interval_secs: u64,
+}
+
+fn default_health_interval() -> u64 {
+    10
+}
+
+/// A spawned, addressable child with its own request correlation state.
+struct ManagedChild {
+    spec: TargetSpec,
+    stdin_tx: mpsc::Sender<String>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    next_id: AtomicU64,
+    child: Mutex<Child>,
+}
+
+impl ManagedChild {
+    fn allocate_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// Owns the fleet of children and the routing layer.
+pub struct MultiTargetManager {
+    validation: Arc<ValidationService>,
+    children: RwLock<HashMap<String, Arc<ManagedChild>>>,
+    config: ManagerConfig,
+}
+
+impl MultiTargetManager {
+    /// Spawn every configured target and start the health monitor.
+    pub async fn start(config: ManagerConfig, validation: Arc<ValidationService>) -> Result<Arc<Self>> {
+        let manager = Arc::new(Self {
+            validation,
+            children: RwLock::new(HashMap::new()),
+            config: config.clone(),
+        });
+
+        for spec in &config.targets {
+            manager.spawn(spec.clone()).await?;
+        }
+
+        manager.clone().start_health_monitor();
+        Ok(manager)
+    }
+
+    /// Route and forward a single request, returning the response to send back
+    /// to the client, or `None` for a notification (an id-less request, which
+    /// gets no reply). `tools/list` and `resources/list` are fanned out to every
+    /// child and merged.
+    pub async fn handle_request(&self, request: &Value) -> Result<Option<Value>> {
+        // Validate before anything leaves Ramparts.
+        let result = self.validation.validate_request(request).await?;
+        if !result.allowed {
+            // A blocked notification has no id to answer; just drop it.
+            if request.get("id").is_none() {
+                return Ok(None);
+            }
+            return Ok(Some(self.validation.create_blocked_response(request, &result)));
+        }
+
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+        if matches!(method, "tools/list" | "resources/list") {
+            let merged = self.aggregate_list(request, method).await?;
+            let result = self.validation.validate_response(&merged).await?;
+            if result.allowed {
+                return Ok(Some(merged));
+            }
+            return Ok(Some(self.validation.create_blocked_response(&merged, &result)));
+        }
+
+        let (target, forwarded) = self.route(request).await?;
+        let Some(child) = self.children.read().await.get(&target).cloned() else {
+            // Nothing to answer for an id-less notification to an unknown target.
+            if request.get("id").is_none() {
+                return Ok(None);
+            }
+            return Ok(Some(self
+                .validation
+                .create_error_response(request, &format!("unknown target server '{}'", target))));
+        };
+
+        // Notifications carry no id and expect no response: forward and return.
+        if request.get("id").is_none() {
+            self.forward_notification(&child, &forwarded).await?;
+            return Ok(None);
+        }
+
+        let response = self.forward(&child, &forwarded, request.get("id").cloned()).await?;
+        let result = self.validation.validate_response(&response).await?;
+        if result.allowed {
+            Ok(Some(response))
+        } else {
+            Ok(Some(self.validation.create_blocked_response(&response, &result)))
+        }
+    }
+
+    /// Determine the target child for a request and strip any routing prefix
+    /// from the method before forwarding.
+    async fn route(&self, request: &Value) -> Result<(String, Value)> {
+        // Preferred: explicit `server` field.
+        if let Some(server) = request.get("server").and_then(Value::as_str) {
+            return Ok((server.to_string(), request.clone()));
+        }
+        // Fallback: a `<server>/<method>` namespace prefix, but only when the
+        // prefix actually names a known child — standard MCP methods such as
+        // `tools/call` or `resources/read` contain a slash too, and must not be
+        // mistaken for a server selector.
+        if let Some(method) = request.get("method").and_then(Value::as_str) {
+            if let Some((prefix, rest)) = method.split_once('/') {
+                if self.children.read().await.contains_key(prefix) {
+                    let mut forwarded = request.clone();
+                    forwarded["method"] = Value::from(rest);
+                    return Ok((prefix.to_string(), forwarded));
+                }
+            }
+        }
+        // No explicit selector: route to the sole target when unambiguous.
+        let children = self.children.read().await;
+        if children.len() == 1 {
+            let server = children.keys().next().expect("len checked").clone();
+            return Ok((server, request.clone()));
+        }
+        Err(anyhow!(
+            "request has no 'server' field and method does not name a known target"
+        ))
+    }
+
+    /// Fan a list request out to every child and merge the arrays under the
+    /// conventional result key (`tools` / `resources`).
+    async fn aggregate_list(&self, request: &Value, method: &str) -> Result<Value> {
+        let key = if method == "tools/list" { "tools" } else { "resources" };
+        let children: Vec<Arc<ManagedChild>> =
+            self.children.read().await.values().cloned().collect();
+
+        let mut merged = Vec::new();
+        for child in children {
+            let forwarded = request.clone();
+            match self.forward(&child, &forwarded, request.get("id").cloned()).await {
+                Ok(resp) => {
+                    if let Some(items) = resp.pointer(&format!("/result/{}", key)).and_then(Value::as_array) {
+                        merged.extend(items.iter().cloned());
+                    }
+                }
+                Err(e) => warn!("Target '{}' failed to answer {}: {}", child.spec.name, method, e),
+            }
+        }
+
+        Ok(json!({
+            "jsonrpc": "2.0",
+            "id": request.get("id"),
+            "result": { key: merged }
+        }))
+    }
+
+    /// Forward one request to a specific child with id remapping and await the
+    /// correlated response, restoring the client's original id.
+    async fn forward(
+        &self,
+        child: &Arc<ManagedChild>,
+        request: &Value,
+        client_id: Option<Value>,
+    ) -> Result<Value> {
+        let relay_id = child.allocate_id();
+        let mut forwarded = request.clone();
+        forwarded["id"] = Value::from(relay_id);
+
+        let (otx, orx) = oneshot::channel();
+        child.pending.lock().await.insert(relay_id, otx);
+
+        let payload = serde_json::to_string(&forwarded)?;
+        if child.stdin_tx.send(payload).await.is_err() {
+            child.pending.lock().await.remove(&relay_id);
+            return Err(anyhow!("target '{}' is not accepting input", child.spec.name));
+        }
+
+        let mut response = tokio::time::timeout(Duration::from_secs(30), orx)
+            .await
+            .map_err(|_| anyhow!("target '{}' timed out", child.spec.name))?
+            .map_err(|_| anyhow!("target '{}' closed before responding", child.spec.name))?;
+        response["id"] = client_id.unwrap_or(Value::Null);
+        Ok(response)
+    }
+
+    /// Forward an id-less notification to a child without allocating a relay id
+    /// or awaiting a response (the MCP server never replies to notifications).
+    async fn forward_notification(&self, child: &Arc<ManagedChild>, request: &Value) -> Result<()> {
+        let payload = serde_json::to_string(request)?;
+        child
+            .stdin_tx
+            .send(payload)
+            .await
+            .map_err(|_| anyhow!("target '{}' is not accepting input", child.spec.name))?;
+        Ok(())
+    }
+
+    /// Spawn (or respawn) a single child and wire up its reader/writer tasks.
+    async fn spawn(&self, spec: TargetSpec) -> Result<()> {
+        debug!("Spawning managed target '{}': {} {:?}", spec.name, spec.command, spec.args);
+        let mut cmd = Command::new(&spec.command);
+        cmd.args(&spec.args)
+            .envs(&spec.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| anyhow!("failed to spawn target '{}': {}", spec.name, e))?;
+
+        let mut child_stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("failed to capture stdin for '{}'", spec.name))?;
+        let child_stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("failed to capture stdout for '{}'", spec.name))?;
+
+        let (stdin_tx, mut stdin_rx) = mpsc::channel::<String>(256);
+        let managed = Arc::new(ManagedChild {
+            spec: spec.clone(),
+            stdin_tx,
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            child: Mutex::new(child),
+        });
+
+        // Writer: frame queued payloads to the child with Content-Length.
+        tokio::spawn(async move {
+            while let Some(payload) = stdin_rx.recv().await {
+                let header = format!("Content-Length: {}\r\n\r\n", payload.len());
+                if child_stdin.write_all(header.as_bytes()).await.is_err()
+                    || child_stdin.write_all(payload.as_bytes()).await.is_err()
+                {
+                    break;
+                }
+                let _ = child_stdin.flush().await;
+            }
+        });
+
+        // Reader: dispatch responses to waiting callers by id.
+        let reader_child = managed.clone();
+        tokio::spawn(async move {
+            read_responses(child_stdout, reader_child).await;
+        });
+
+        self.children.write().await.insert(spec.name.clone(), managed);
+        info!("Managed target '{}' is up", spec.name);
+        Ok(())
+    }
+
+    /// Periodically reap and restart exited children so the fleet self-heals.
+    fn start_health_monitor(self: Arc<Self>) {
+        let interval = Duration::from_secs(self.config.health_interval_secs.max(1));
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let specs: Vec<(String, TargetSpec)> = {
+                    let children = self.children.read().await;
+                    children
+                        .iter()
+                        .map(|(n, c)| (n.clone(), c.spec.clone()))
+                        .collect()
+                };
+                for (name, spec) in specs {
+                    let dead = {
+                        let children = self.children.read().await;
+                        match children.get(&name) {
+                            Some(c) => c.child.lock().await.try_wait().ok().flatten().is_some(),
+                            None => false,
+                        }
+                    };
+                    if dead {
+                        warn!("Target '{}' exited, restarting", name);
+                        if let Err(e) = self.spawn(spec).await {
+                            error!("Failed to restart target '{}': {}", name, e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Read Content-Length framed messages from a child's stdout and route each to
+/// its pending caller.
+async fn read_responses(stdout: ChildStdout, child: Arc<ManagedChild>) {
+    let mut reader = BufReader::new(stdout);
+    loop {
+        match read_framed(&mut reader).await {
+            Some(payload) => {
+                if let Ok(mut value) = serde_json::from_str::<Value>(&payload) {
+                    if let Some(id) = value.get("id").and_then(Value::as_u64) {
+                        if let Some(sender) = child.pending.lock().await.remove(&id) {
+                            let _ = sender.send(value.take());
+                        }
+                    }
+                }
+            }
+            None => break,
+        }
+    }
+    debug!("Reader for target '{}' stopped", child.spec.name);
+}
+
+/// Read one Content-Length framed message, or `None` on EOF/error.
+async fn read_framed(reader: &mut BufReader<ChildStdout>) -> Option<String> {
+    use tokio::io::AsyncBufReadExt;
+    let mut content_length: Option<usize> = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        match reader.read_line(&mut header).await {
+            Ok(0) => return None,
+            Ok(_) => {
+                let line = header.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(rest) = line.strip_prefix("Content-Length:") {
+                    content_length = rest.trim().parse().ok();
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+    let len = content_length?;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).await.ok()?;
+    Some(String::from_utf8_lossy(&buf).to_string())
+}
@@ -0,0 +1,362 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Where a validated request originated, so operator dashboards can exclude
+/// Ramparts' own health checks and cache-warm traffic from real client load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestSource {
+    Client,
+    Internal,
+}
+
+impl RequestSource {
+    fn label(self) -> &'static str {
+        match self {
+            RequestSource::Client => "client",
+            RequestSource::Internal => "internal",
+        }
+    }
+}
+
+/// Coarse reason a request was blocked, used as a low-cardinality metric label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockReason {
+    DangerousTool,
+    Injection,
+    PathTraversal,
+    PromptInjection,
+    Ssrf,
+    JavelinDenied,
+    FailOpen,
+    FailClosed,
+}
+
+impl BlockReason {
+    fn label(self) -> &'static str {
+        match self {
+            BlockReason::DangerousTool => "dangerous-tool",
+            BlockReason::Injection => "injection",
+            BlockReason::PathTraversal => "path-traversal",
+            BlockReason::PromptInjection => "prompt-injection",
+            BlockReason::Ssrf => "ssrf",
+            BlockReason::JavelinDenied => "javelin-denied",
+            BlockReason::FailOpen => "fail-open",
+            BlockReason::FailClosed => "fail-closed",
+        }
+    }
+
+    /// Best-effort classification of a free-form `ValidationResult.reason` into a
+    /// coarse label. Keeps the label set bounded even as reasons gain detail.
+    pub fn classify(reason: &str) -> BlockReason {
+        let r = reason.to_ascii_lowercase();
+        // Policy-rule blocks surface as "... blocked by policy rule '<name>'";
+        // key on the built-in rule names.
+        if r.contains("dangerous-tool") {
+            BlockReason::DangerousTool
+        } else if r.contains("prompt-injection") {
+            BlockReason::PromptInjection
+        } else if r.contains("command-injection") {
+            BlockReason::Injection
+        } else if r.contains("path-traversal") {
+            BlockReason::PathTraversal
+        } else if r.contains("blocked host") || r.contains("ssrf") {
+            BlockReason::Ssrf
+        } else if r.contains("failing open") {
+            BlockReason::FailOpen
+        } else if r.contains("failing closed") {
+            BlockReason::FailClosed
+        } else {
+            BlockReason::JavelinDenied
+        }
+    }
+}
+
+/// Fixed latency histogram buckets (seconds) for the Javelin validation call.
+const LATENCY_BUCKETS: [f64; 9] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+#[derive(Default)]
+struct Counters {
+    allow: AtomicU64,
+    block: AtomicU64,
+}
+
+#[derive(Default)]
+struct Latency {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Latency {
+    fn observe(&self, seconds: f64) {
+        for (i, upper) in LATENCY_BUCKETS.iter().enumerate() {
+            if seconds <= *upper {
+                self.buckets[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add((seconds * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+}
+
+/// Per-method and per-reason validation counters plus a Javelin-call latency
+/// histogram. Shared across tasks and updated on every validate call.
+pub struct ValidationMetrics {
+    // method -> source -> counters
+    by_method: RwLock<HashMap<(String, RequestSource), Counters>>,
+    by_reason: RwLock<HashMap<(BlockReason, RequestSource), AtomicU64>>,
+    latency: Latency,
+}
+
+impl ValidationMetrics {
+    pub fn new() -> Self {
+        Self {
+            by_method: RwLock::new(HashMap::new()),
+            by_reason: RwLock::new(HashMap::new()),
+            latency: Latency::default(),
+        }
+    }
+
+    /// Record an allow decision for `method` from `source`.
+    pub fn record_allow(&self, method: &str, source: RequestSource) {
+        self.with_method(method, source, |c| {
+            c.allow.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    /// Record a block decision, incrementing both the per-method block counter
+    /// and the per-reason counter.
+    pub fn record_block(&self, method: &str, reason: BlockReason, source: RequestSource) {
+        self.with_method(method, source, |c| {
+            c.block.fetch_add(1, Ordering::Relaxed);
+        });
+        let mut map = self.by_reason.write().unwrap();
+        map.entry((reason, source))
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Observe the latency of a single `javelin_client.validate_request` await.
+    pub fn observe_javelin_latency(&self, seconds: f64) {
+        self.latency.observe(seconds);
+    }
+
+    fn with_method(&self, method: &str, source: RequestSource, f: impl FnOnce(&Counters)) {
+        let key = (method.to_string(), source);
+        {
+            if let Some(c) = self.by_method.read().unwrap().get(&key) {
+                f(c);
+                return;
+            }
+        }
+        let mut map = self.by_method.write().unwrap();
+        let entry = map.entry(key).or_default();
+        f(entry);
+    }
+
+    /// Capture a point-in-time, serializable view of all counters.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let by_method = self
+            .by_method
+            .read()
+            .unwrap()
+            .iter()
+            .map(|((method, source), c)| MethodSnapshot {
+                method: method.clone(),
+                source: source.label().to_string(),
+                allow: c.allow.load(Ordering::Relaxed),
+                block: c.block.load(Ordering::Relaxed),
+            })
+            .collect();
+        let by_reason = self
+            .by_reason
+            .read()
+            .unwrap()
+            .iter()
+            .map(|((reason, source), c)| ReasonSnapshot {
+                reason: reason.label().to_string(),
+                source: source.label().to_string(),
+                count: c.load(Ordering::Relaxed),
+            })
+            .collect();
+        MetricsSnapshot {
+            by_method,
+            by_reason,
+            latency_count: self.latency.count.load(Ordering::Relaxed),
+            latency_sum_seconds: self.latency.sum_micros.load(Ordering::Relaxed) as f64 / 1e6,
+        }
+    }
+
+    /// Render the full metric set in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# HELP ramparts_validation_requests_total Validation decisions by method and outcome."
+        );
+        let _ = writeln!(out, "# TYPE ramparts_validation_requests_total counter");
+        for ((method, source), c) in self.by_method.read().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "ramparts_validation_requests_total{{method=\"{}\",source=\"{}\",outcome=\"allow\"}} {}",
+                method,
+                source.label(),
+                c.allow.load(Ordering::Relaxed)
+            );
+            let _ = writeln!(
+                out,
+                "ramparts_validation_requests_total{{method=\"{}\",source=\"{}\",outcome=\"block\"}} {}",
+                method,
+                source.label(),
+                c.block.load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP ramparts_validation_blocks_total Blocks by coarse reason."
+        );
+        let _ = writeln!(out, "# TYPE ramparts_validation_blocks_total counter");
+        for ((reason, source), c) in self.by_reason.read().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "ramparts_validation_blocks_total{{reason=\"{}\",source=\"{}\"}} {}",
+                reason.label(),
+                source.label(),
+                c.load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP ramparts_validation_javelin_latency_seconds Latency of the Javelin validation call."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE ramparts_validation_javelin_latency_seconds histogram"
+        );
+        let mut cumulative;
+        for (i, upper) in LATENCY_BUCKETS.iter().enumerate() {
+            cumulative = self.latency.buckets[i].load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "ramparts_validation_javelin_latency_seconds_bucket{{le=\"{}\"}} {}",
+                upper, cumulative
+            );
+        }
+        let count = self.latency.count.load(Ordering::Relaxed);
+        let _ = writeln!(
+            out,
+            "ramparts_validation_javelin_latency_seconds_bucket{{le=\"+Inf\"}} {}",
+            count
+        );
+        let _ = writeln!(
+            out,
+            "ramparts_validation_javelin_latency_seconds_sum {}",
+            self.latency.sum_micros.load(Ordering::Relaxed) as f64 / 1e6
+        );
+        let _ = writeln!(
+            out,
+            "ramparts_validation_javelin_latency_seconds_count {}",
+            count
+        );
+        out
+    }
+}
+
+impl Default for ValidationMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializable snapshot returned from [`ValidationMetrics::snapshot`].
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    pub by_method: Vec<MethodSnapshot>,
+    pub by_reason: Vec<ReasonSnapshot>,
+    pub latency_count: u64,
+    pub latency_sum_seconds: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MethodSnapshot {
+    pub method: String,
+    pub source: String,
+    pub allow: u64,
+    pub block: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReasonSnapshot {
+    pub reason: String,
+    pub source: String,
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_reasons() {
+        assert_eq!(
+            BlockReason::classify("Tool 'rm' blocked by policy rule 'dangerous-tool'"),
+            BlockReason::DangerousTool
+        );
+        assert_eq!(
+            BlockReason::classify("Tool 'x' arguments blocked by policy rule 'command-injection'"),
+            BlockReason::Injection
+        );
+        assert_eq!(
+            BlockReason::classify("Resource URI '..' blocked by policy rule 'path-traversal'"),
+            BlockReason::PathTraversal
+        );
+        assert_eq!(
+            BlockReason::classify("Prompt 'x' blocked by policy rule 'prompt-injection'"),
+            BlockReason::PromptInjection
+        );
+        assert_eq!(
+            BlockReason::classify("Validation service unavailable, failing open: x"),
+            BlockReason::FailOpen
+        );
+        assert_eq!(
+            BlockReason::classify("Tool 'fetch' arguments target blocked host 'x'"),
+            BlockReason::Ssrf
+        );
+    }
+
+    #[test]
+    fn test_counters_and_snapshot() {
+        let m = ValidationMetrics::new();
+        m.record_allow("tools/call", RequestSource::Client);
+        m.record_allow("tools/call", RequestSource::Client);
+        m.record_block("tools/call", BlockReason::DangerousTool, RequestSource::Client);
+        m.observe_javelin_latency(0.02);
+
+        let snap = m.snapshot();
+        let method = snap
+            .by_method
+            .iter()
+            .find(|s| s.method == "tools/call" && s.source == "client")
+            .unwrap();
+        assert_eq!(method.allow, 2);
+        assert_eq!(method.block, 1);
+        assert_eq!(snap.latency_count, 1);
+    }
+
+    #[test]
+    fn test_prometheus_render_contains_series() {
+        let m = ValidationMetrics::new();
+        m.record_block("resources/read", BlockReason::PathTraversal, RequestSource::Client);
+        let text = m.render_prometheus();
+        assert!(text.contains("ramparts_validation_requests_total"));
+        assert!(text.contains("reason=\"path-traversal\""));
+    }
+}
@@ -0,0 +1,296 @@
+//! Operator-tunable policy engine that replaces the hardcoded dangerous-tool
+//! and injection lists. Rules are loaded from a YAML/JSON file referenced by
+//! `ProxyConfig`, compiled to regexes with word-boundary semantics to cut the
+//! substring false positives of the old inline arrays (e.g. a tool named
+//! `format_date` no longer trips the `format` rule), and hot-reloaded when the
+//! file changes on disk.
+
+use ramparts_common::{
+    anyhow::{anyhow, Context, Result},
+    tracing::{info, warn},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Which part of a request a rule matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleTarget {
+    ToolName,
+    ToolArguments,
+    ResourceUri,
+    PromptName,
+}
+
+/// What to do when a rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Reject the request immediately.
+    Block,
+    /// Let the request continue to Javelin; the match is logged but not
+    /// recorded on the request or the validation result.
+    Flag,
+}
+
+/// Rule severity, mapped to a confidence value via `severity_confidence`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// A single policy rule as written in the config file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    pub name: String,
+    pub target: RuleTarget,
+    /// Regex matched (case-insensitively) against the target string.
+    pub pattern: String,
+    #[serde(default = "default_action")]
+    pub action: Action,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+}
+
+fn default_action() -> Action {
+    Action::Block
+}
+
+fn default_severity() -> Severity {
+    Severity::High
+}
+
+/// Raw policy file contents (YAML or JSON).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyFile {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    #[serde(default)]
+    pub severity_confidence: HashMap<Severity, f64>,
+}
+
+/// Policy configuration carried on `ProxyConfig`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    /// Path to a YAML/JSON rules file. When unset the built-in defaults apply.
+    pub file: Option<PathBuf>,
+}
+
+/// A rule whose pattern has been compiled.
+struct CompiledRule {
+    rule: Rule,
+    regex: Regex,
+}
+
+/// The live, compiled policy behind an `RwLock` so hot-reload can swap it.
+struct Compiled {
+    rules: Vec<CompiledRule>,
+    severity_confidence: HashMap<Severity, f64>,
+}
+
+/// Outcome of evaluating a request against the policy.
+#[derive(Debug, Clone)]
+pub struct PolicyDecision {
+    pub rule_name: String,
+    pub action: Action,
+    pub confidence: f64,
+}
+
+/// Loads, compiles, evaluates, and hot-reloads the policy.
+pub struct PolicyEngine {
+    compiled: Arc<RwLock<Compiled>>,
+    // Kept alive for the lifetime of the engine so the watch thread keeps running.
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl PolicyEngine {
+    /// Build the engine from config, falling back to the built-in defaults when
+    /// no file is configured. When a file is configured, a filesystem watcher
+    /// is installed so edits take effect without a restart.
+    pub fn from_config(config: &PolicyConfig) -> Result<Self> {
+        let Some(path) = config.file.clone() else {
+            return Ok(Self {
+                compiled: Arc::new(RwLock::new(compile(default_policy())?)),
+                _watcher: None,
+            });
+        };
+
+        let policy = load_file(&path)?;
+        let compiled = Arc::new(RwLock::new(compile(policy)?));
+
+        // Hot-reload: recompile on any change to the rules file.
+        let watch_target = path.clone();
+        let slot = compiled.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(_) => match load_file(&watch_target).and_then(compile) {
+                    Ok(fresh) => {
+                        if let Ok(mut guard) = slot.write() {
+                            *guard = fresh;
+                            info!("Reloaded policy from {}", watch_target.display());
+                        }
+                    }
+                    Err(e) => warn!("Ignoring invalid policy reload: {}", e),
+                },
+                Err(e) => warn!("Policy watch error: {}", e),
+            }
+        })
+        .map_err(|e| anyhow!("failed to create policy watcher: {}", e))?;
+
+        use notify::Watcher;
+        watcher
+            .watch(&path, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| anyhow!("failed to watch policy file {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            compiled,
+            _watcher: Some(watcher),
+        })
+    }
+
+    /// Evaluate `value` against every rule targeting `target`, returning the
+    /// first match (block rules take priority over flag rules).
+    pub fn evaluate(&self, target: RuleTarget, value: &str) -> Option<PolicyDecision> {
+        let guard = self.compiled.read().ok()?;
+        let mut flagged: Option<PolicyDecision> = None;
+        for cr in guard.rules.iter().filter(|r| r.rule.target == target) {
+            if cr.regex.is_match(value) {
+                let confidence = confidence_for(&guard.severity_confidence, cr.rule.severity);
+                let decision = PolicyDecision {
+                    rule_name: cr.rule.name.clone(),
+                    action: cr.rule.action,
+                    confidence,
+                };
+                match cr.rule.action {
+                    Action::Block => return Some(decision),
+                    Action::Flag => flagged.get_or_insert(decision),
+                };
+            }
+        }
+        flagged
+    }
+}
+
+fn confidence_for(map: &HashMap<Severity, f64>, severity: Severity) -> f64 {
+    map.get(&severity).copied().unwrap_or(match severity {
+        Severity::Low => 0.5,
+        Severity::Medium => 0.7,
+        Severity::High => 0.9,
+        Severity::Critical => 0.99,
+    })
+}
+
+fn load_file(path: &PathBuf) -> Result<PolicyFile> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("reading policy file {}", path.display()))?;
+    let is_json = path.extension().map(|e| e == "json").unwrap_or(false);
+    if is_json {
+        // Lenient JSONC so operators can annotate their rules in place.
+        let value = crate::jsonc::parse_jsonc(&text)
+            .with_context(|| format!("parsing JSON policy {}", path.display()))?;
+        serde_json::from_value(value)
+            .with_context(|| format!("parsing JSON policy {}", path.display()))
+    } else {
+        serde_yaml::from_str(&text).with_context(|| format!("parsing YAML policy {}", path.display()))
+    }
+}
+
+fn compile(policy: PolicyFile) -> Result<Compiled> {
+    let mut rules = Vec::with_capacity(policy.rules.len());
+    for rule in policy.rules {
+        let regex = Regex::new(&format!("(?i){}", rule.pattern))
+            .with_context(|| format!("compiling pattern for rule '{}'", rule.name))?;
+        rules.push(CompiledRule { rule, regex });
+    }
+    Ok(Compiled {
+        rules,
+        severity_confidence: policy.severity_confidence,
+    })
+}
+
+/// Built-in rule set, equivalent to the previous inline arrays but with
+/// word-boundary matching so partial words no longer over-block.
+fn default_policy() -> PolicyFile {
+    let rule = |name: &str, target, pattern: &str, severity| Rule {
+        name: name.to_string(),
+        target,
+        pattern: pattern.to_string(),
+        action: Action::Block,
+        severity,
+    };
+    PolicyFile {
+        rules: vec![
+            rule(
+                "dangerous-tool",
+                RuleTarget::ToolName,
+                r"\b(exec|shell|bash|cmd|powershell|eval|system|subprocess|popen|spawn|fork|kill|rm|del|format|fdisk|mkfs|dd|nc|netcat|telnet|curl_exec|wget_exec|download_exec)\b",
+                Severity::High,
+            ),
+            rule(
+                "command-injection",
+                RuleTarget::ToolArguments,
+                r"(;\s|\|\s|&\s|\$\(|`|&&|\|\||\.\./|\.\.\\|rm\s-|del\s|mkfs|dd\sif=|\bcurl\b|\bwget\b|\bnc\b|netcat|telnet|\bssh\b|base64|\beval\b|\bexec\b|\bsystem\b|popen)",
+                Severity::High,
+            ),
+            rule(
+                "path-traversal",
+                RuleTarget::ResourceUri,
+                r"(\.\./|\.\.\\|%2e%2e|\.\.\.\.|/etc/|\\windows\\|/proc/|/sys/)",
+                Severity::High,
+            ),
+            rule(
+                "prompt-injection",
+                RuleTarget::PromptName,
+                r"(\bignore\b|\bforget\b|\bdisregard\b|\boverride\b|\bbypass\b|\bjailbreak\b|system:|assistant:|user:|human:|ai:|chatgpt:|\\n\\n|---|###|```|\bexec\b|\beval\b|\bscript\b)",
+                Severity::Medium,
+            ),
+        ],
+        severity_confidence: HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> PolicyEngine {
+        PolicyEngine {
+            compiled: Arc::new(RwLock::new(compile(default_policy()).unwrap())),
+            _watcher: None,
+        }
+    }
+
+    #[test]
+    fn test_word_boundary_avoids_false_positive() {
+        let e = engine();
+        // Previously "format" matched any name containing it.
+        assert!(e.evaluate(RuleTarget::ToolName, "format_date").is_none());
+        let hit = e.evaluate(RuleTarget::ToolName, "format").unwrap();
+        assert_eq!(hit.rule_name, "dangerous-tool");
+    }
+
+    #[test]
+    fn test_path_traversal_rule() {
+        let e = engine();
+        assert!(e
+            .evaluate(RuleTarget::ResourceUri, "file:///etc/passwd")
+            .is_some());
+        assert!(e
+            .evaluate(RuleTarget::ResourceUri, "file:///home/app/data.txt")
+            .is_none());
+    }
+
+    #[test]
+    fn test_confidence_defaults_by_severity() {
+        let map = HashMap::new();
+        assert_eq!(confidence_for(&map, Severity::High), 0.9);
+        assert_eq!(confidence_for(&map, Severity::Critical), 0.99);
+    }
+}
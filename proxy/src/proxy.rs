@@ -1,7 +1,7 @@
 use crate::{get_license_status, GuardedMcpServer, JavelinClient, ProxyConfig, ValidationService};
 use axum::{
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
     routing::{any_service, get, post},
     Router,
@@ -68,6 +68,17 @@ impl MCPProxy {
         Ok(Self { config, mcp_server })
     }
 
+    /// Run in reverse-connect relay mode: MCP server endpoints dial into
+    /// Ramparts and register by name, and clients are routed to them through
+    /// [`ValidationService`] in both directions.
+    pub async fn start_relay(&self) -> Result<()> {
+        let validation_service = Arc::new(ValidationService::new(
+            self.mcp_server.get_javelin_client(),
+            self.config.clone(),
+        ));
+        crate::relay::serve(self.config.relay.clone(), validation_service).await
+    }
+
     pub async fn start(&self) -> Result<()> {
         info!(
             "Starting Ramparts AI Gateway on {} (security-first MCP proxy)",
@@ -101,6 +112,8 @@ impl MCPProxy {
             .route("/", get(health_check))
             .route("/health", get(health_check))
             .route("/license", get(license_status))
+            .route("/keys/status", get(keys_status))
+            .route("/metrics", get(metrics))
             .route("/validate", post(validate_request))
             // MCP endpoint with enterprise security validation
             .route("/mcp", any_service(mcp_service))
@@ -125,6 +138,8 @@ impl MCPProxy {
         info!("  - /mcp (Secure MCP protocol with Javelin Guardrails)");
         info!("  - /health (Health check)");
         info!("  - /license (License status)");
+        info!("  - /keys/status (Scoped API key status)");
+        info!("  - /metrics (Prometheus validation metrics)");
         info!("  - /validate (Enterprise request validation)");
 
         // Start the server
@@ -161,9 +176,50 @@ async fn license_status() -> Json<Value> {
     }))
 }
 
+/// Status of the scoped API keys, mirroring the `/license` handler.
+async fn keys_status(State(state): State<ProxyState>) -> Json<Value> {
+    match state.validation_service.key_store() {
+        Some(store) => Json(json!({
+            "keys": store.status().get("keys").cloned().unwrap_or(Value::Null),
+            "enforcement": "enabled",
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+        None => Json(json!({
+            "keys": [],
+            "enforcement": "disabled",
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })),
+    }
+}
+
+/// Extract the presented Ramparts API key from request headers.
+fn extract_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get("x-ramparts-key")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+}
+
+/// Prometheus text-format metrics for validation decisions and latency.
+async fn metrics(State(state): State<ProxyState>) -> (StatusCode, [(&'static str, &'static str); 1], String) {
+    let mut body = state.validation_service.metrics().render_prometheus();
+    body.push_str(&crate::proxy_metrics::proxy_metrics().render_prometheus());
+    (
+        StatusCode::OK,
+        [("content-type", "text/plain; version=0.0.4")],
+        body,
+    )
+}
+
 /// Validate a request against Javelin Guardrails
 async fn validate_request(
     State(state): State<ProxyState>,
+    headers: HeaderMap,
     Json(request): Json<Value>,
 ) -> Result<Json<Value>, StatusCode> {
     // Redact sensitive values before logging
@@ -173,19 +229,45 @@ async fn validate_request(
         serde_json::to_string_pretty(&redacted).unwrap_or_default()
     );
 
+    // Enforce scoped API keys before consulting the guardrails service.
+    if let Some(denied) = state
+        .validation_service
+        .authorize_key(extract_api_key(&headers), &request)
+    {
+        return Ok(Json(json!({
+            "valid": false,
+            "reason": denied.reason,
+            "confidence": denied.confidence,
+            "request_id": denied.request_id,
+            "timestamp": denied.timestamp
+        })));
+    }
+
+    let metrics = crate::proxy_metrics::proxy_metrics();
     match state.validation_service.validate_request(&request).await {
         Ok(result) => {
+            if result.allowed {
+                metrics.inc_forwarded();
+            } else {
+                metrics.inc_blocked();
+            }
             let response = json!({
                 "valid": result.allowed,
                 "reason": result.reason,
                 "confidence": result.confidence,
                 "request_id": result.request_id,
-                "timestamp": result.timestamp
+                "timestamp": result.timestamp,
+                // Only meaningful when blocked; lets callers tell a fail-closed
+                // infrastructure failure apart from a genuine policy denial.
+                "category": (!result.allowed).then(|| result.category.as_str()),
+                "retryable": !result.allowed
+                    && result.category == crate::validation_service::DenialCategory::ValidationUnavailable
             });
             Ok(Json(response))
         }
         Err(e) => {
             error!("Validation error: {}", e);
+            metrics.inc_validation_error();
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
@@ -0,0 +1,182 @@
+//! Lock-free transport-layer metrics for the proxy request/response loops.
+//!
+//! The per-request counting in the proxy loops must not contend on the
+//! `Arc<Mutex<HashMap>>` request tracker, so counts live here in plain
+//! [`AtomicU64`]s plus a small fixed-method latency histogram. A process-global
+//! registry ([`proxy_metrics`]) lets both the stdio loops and the axum handlers
+//! update the same counters without threading a handle everywhere, and the
+//! `/metrics` route renders them in Prometheus text format.
+
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+/// Methods tracked with their own latency histogram. Anything else folds into
+/// `other`, keeping the registry a fixed-size, allocation-free structure.
+const TRACKED_METHODS: [&str; 8] = [
+    "initialize",
+    "tools/list",
+    "tools/call",
+    "resources/list",
+    "resources/read",
+    "prompts/get",
+    "prompts/list",
+    "other",
+];
+
+const LATENCY_BUCKETS: [f64; 8] = [0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, seconds: f64) {
+        for (i, upper) in LATENCY_BUCKETS.iter().enumerate() {
+            if seconds <= *upper {
+                self.buckets[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add((seconds * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+}
+
+/// All transport counters. Every field is updated with relaxed atomics.
+pub struct ProxyMetrics {
+    pub requests_forwarded: AtomicU64,
+    pub requests_blocked: AtomicU64,
+    pub fail_open_passthrough: AtomicU64,
+    pub validation_errors: AtomicU64,
+    pub responses_forwarded: AtomicU64,
+    pub responses_blocked: AtomicU64,
+    latency: [Histogram; TRACKED_METHODS.len()],
+}
+
+impl ProxyMetrics {
+    fn new() -> Self {
+        Self {
+            requests_forwarded: AtomicU64::new(0),
+            requests_blocked: AtomicU64::new(0),
+            fail_open_passthrough: AtomicU64::new(0),
+            validation_errors: AtomicU64::new(0),
+            responses_forwarded: AtomicU64::new(0),
+            responses_blocked: AtomicU64::new(0),
+            latency: Default::default(),
+        }
+    }
+
+    pub fn inc_forwarded(&self) {
+        self.requests_forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn inc_blocked(&self) {
+        self.requests_blocked.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn inc_fail_open(&self) {
+        self.fail_open_passthrough.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn inc_validation_error(&self) {
+        self.validation_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn inc_response_forwarded(&self) {
+        self.responses_forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn inc_response_blocked(&self) {
+        self.responses_blocked.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Observe request-handling latency for a method (lock-free).
+    pub fn observe_latency(&self, method: &str, seconds: f64) {
+        self.latency[method_index(method)].observe(seconds);
+    }
+
+    /// Render all counters and histograms in Prometheus text format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let counters: [(&str, &AtomicU64); 6] = [
+            ("ramparts_proxy_requests_forwarded_total", &self.requests_forwarded),
+            ("ramparts_proxy_requests_blocked_total", &self.requests_blocked),
+            ("ramparts_proxy_fail_open_passthrough_total", &self.fail_open_passthrough),
+            ("ramparts_proxy_validation_errors_total", &self.validation_errors),
+            ("ramparts_proxy_responses_forwarded_total", &self.responses_forwarded),
+            ("ramparts_proxy_responses_blocked_total", &self.responses_blocked),
+        ];
+        for (name, counter) in counters {
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            let _ = writeln!(out, "{} {}", name, counter.load(Ordering::Relaxed));
+        }
+
+        let _ = writeln!(
+            out,
+            "# TYPE ramparts_proxy_request_latency_seconds histogram"
+        );
+        for (m, method) in TRACKED_METHODS.iter().enumerate() {
+            let hist = &self.latency[m];
+            for (i, upper) in LATENCY_BUCKETS.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "ramparts_proxy_request_latency_seconds_bucket{{method=\"{}\",le=\"{}\"}} {}",
+                    method,
+                    upper,
+                    hist.buckets[i].load(Ordering::Relaxed)
+                );
+            }
+            let count = hist.count.load(Ordering::Relaxed);
+            let _ = writeln!(
+                out,
+                "ramparts_proxy_request_latency_seconds_bucket{{method=\"{}\",le=\"+Inf\"}} {}",
+                method, count
+            );
+            let _ = writeln!(
+                out,
+                "ramparts_proxy_request_latency_seconds_sum{{method=\"{}\"}} {}",
+                method,
+                hist.sum_micros.load(Ordering::Relaxed) as f64 / 1e6
+            );
+            let _ = writeln!(
+                out,
+                "ramparts_proxy_request_latency_seconds_count{{method=\"{}\"}} {}",
+                method, count
+            );
+        }
+        out
+    }
+}
+
+fn method_index(method: &str) -> usize {
+    TRACKED_METHODS
+        .iter()
+        .position(|m| *m == method)
+        .unwrap_or(TRACKED_METHODS.len() - 1) // "other"
+}
+
+/// The process-global transport metrics registry.
+pub fn proxy_metrics() -> &'static ProxyMetrics {
+    static METRICS: OnceLock<ProxyMetrics> = OnceLock::new();
+    METRICS.get_or_init(ProxyMetrics::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_method_index_falls_back_to_other() {
+        assert_eq!(method_index("tools/call"), 2);
+        assert_eq!(method_index("nonsense"), TRACKED_METHODS.len() - 1);
+    }
+
+    #[test]
+    fn test_render_contains_counters() {
+        let m = ProxyMetrics::new();
+        m.inc_forwarded();
+        m.observe_latency("tools/call", 0.02);
+        let text = m.render_prometheus();
+        assert!(text.contains("ramparts_proxy_requests_forwarded_total 1"));
+        assert!(text.contains("method=\"tools/call\""));
+    }
+}
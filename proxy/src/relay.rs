@@ -0,0 +1,349 @@
+//! Reverse-connect relay mode.
+//!
+//! Instead of Ramparts spawning or dialing MCP servers, the servers dial *out*
+//! to Ramparts and register under a name over the relay listener. MCP clients
+//! connect to a separate client-facing listener and each request is routed to
+//! the matching registered endpoint, validated in both directions through
+//! [`ValidationService`]. This guards MCP servers running in private networks
+//! without giving Ramparts the ability to reach them directly.
+
+use ramparts_common::{
+    anyhow::{anyhow, Result},
+    tracing::{debug, error, info, warn},
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+
+use crate::logging::SensitiveString;
+use crate::ValidationService;
+
+/// Relay configuration carried on `ProxyConfig`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct RelayConfig {
+    /// Address remote MCP server endpoints dial into to register.
+    pub endpoint_listen: Option<String>,
+    /// Address MCP clients connect to.
+    pub client_listen: Option<String>,
+    /// Shared secret a dialing endpoint must present in its registration line.
+    /// Stored as a [`SensitiveString`] so it is redacted by construction in
+    /// logs and `Debug` output. When unset, registration is unauthenticated.
+    #[serde(default)]
+    pub registration_token: Option<SensitiveString>,
+}
+
+/// A registered, connected server endpoint.
+struct Endpoint {
+    /// Frames queued for delivery to the endpoint socket.
+    tx: mpsc::Sender<String>,
+    /// Outbound request id -> the client waiting for that response. Mirrors the
+    /// stdio proxy's `request_tracker`, but keyed on relay-assigned ids so two
+    /// clients can't collide.
+    pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
+    next_id: AtomicU64,
+}
+
+impl Endpoint {
+    fn allocate_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// name -> connected endpoint. Shared across the relay and client listeners.
+#[derive(Default)]
+pub struct RelayRegistry {
+    endpoints: RwLock<HashMap<String, Arc<Endpoint>>>,
+}
+
+impl RelayRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn register(&self, name: String, endpoint: Arc<Endpoint>) {
+        // A reconnecting endpoint replaces the previous registration.
+        if self.endpoints.write().await.insert(name.clone(), endpoint).is_some() {
+            warn!("Relay endpoint '{}' re-registered, replacing previous connection", name);
+        } else {
+            info!("Relay endpoint '{}' registered", name);
+        }
+    }
+
+    async fn deregister(&self, name: &str) {
+        self.endpoints.write().await.remove(name);
+        info!("Relay endpoint '{}' disconnected", name);
+    }
+
+    async fn get(&self, name: &str) -> Option<Arc<Endpoint>> {
+        self.endpoints.read().await.get(name).cloned()
+    }
+}
+
+/// Start both relay listeners and serve until one of them errors.
+pub async fn serve(
+    config: RelayConfig,
+    validation: Arc<ValidationService>,
+) -> Result<()> {
+    let registry = Arc::new(RelayRegistry::new());
+
+    let registration_token = config.registration_token.clone();
+
+    let endpoint_addr = config
+        .endpoint_listen
+        .ok_or_else(|| anyhow!("relay.endpoint_listen is required for relay mode"))?;
+    let client_addr = config
+        .client_listen
+        .ok_or_else(|| anyhow!("relay.client_listen is required for relay mode"))?;
+
+    let endpoint_listener = TcpListener::bind(&endpoint_addr)
+        .await
+        .map_err(|e| anyhow!("failed to bind relay endpoint listener {}: {}", endpoint_addr, e))?;
+    let client_listener = TcpListener::bind(&client_addr)
+        .await
+        .map_err(|e| anyhow!("failed to bind relay client listener {}: {}", client_addr, e))?;
+
+    info!(
+        "Relay mode: endpoints dial in on {}, clients connect on {}",
+        endpoint_addr, client_addr
+    );
+
+    let endpoint_registry = registry.clone();
+    let endpoint_task = tokio::spawn(async move {
+        accept_endpoints(endpoint_listener, endpoint_registry, registration_token).await
+    });
+
+    let client_task = tokio::spawn(async move {
+        accept_clients(client_listener, registry, validation).await
+    });
+
+    tokio::select! {
+        r = endpoint_task => r.map_err(|e| anyhow!("endpoint listener panicked: {}", e))?,
+        r = client_task => r.map_err(|e| anyhow!("client listener panicked: {}", e))?,
+    }
+}
+
+/// Accept and service endpoint (server) connections.
+async fn accept_endpoints(
+    listener: TcpListener,
+    registry: Arc<RelayRegistry>,
+    registration_token: Option<SensitiveString>,
+) -> Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        debug!("Relay endpoint connection from {}", peer);
+        let registry = registry.clone();
+        let registration_token = registration_token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_endpoint(stream, registry, registration_token).await {
+                error!("Relay endpoint connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// First line from an endpoint is a registration
+/// `{ "register": "<name>", "token": "<secret>" }`; subsequent lines are
+/// JSON-RPC responses routed back to waiting clients.
+async fn handle_endpoint(
+    stream: TcpStream,
+    registry: Arc<RelayRegistry>,
+    registration_token: Option<SensitiveString>,
+) -> Result<()> {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+
+    let mut first = String::new();
+    if reader.read_line(&mut first).await? == 0 {
+        return Ok(());
+    }
+    let registration = serde_json::from_str::<Value>(first.trim())
+        .map_err(|e| anyhow!("endpoint sent a malformed registration line: {}", e))?;
+    let name = registration
+        .get("register")
+        .and_then(|n| n.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("endpoint did not send a valid registration line"))?;
+
+    // Authenticate the endpoint before it can register a name and receive
+    // traffic. Compared against the configured shared secret when one is set.
+    if let Some(expected) = &registration_token {
+        let presented = registration.get("token").and_then(|t| t.as_str());
+        if presented != Some(expected.inner()) {
+            return Err(anyhow!("endpoint '{}' presented an invalid registration token", name));
+        }
+    }
+
+    let (tx, mut rx) = mpsc::channel::<String>(256);
+    let endpoint = Arc::new(Endpoint {
+        tx,
+        pending: Mutex::new(HashMap::new()),
+        next_id: AtomicU64::new(1),
+    });
+    registry.register(name.clone(), endpoint.clone()).await;
+
+    // Writer: drain queued frames to the endpoint socket.
+    let writer = tokio::spawn(async move {
+        while let Some(frame) = rx.recv().await {
+            if write_half.write_all(frame.as_bytes()).await.is_err()
+                || write_half.write_all(b"\n").await.is_err()
+            {
+                break;
+            }
+            let _ = write_half.flush().await;
+        }
+    });
+
+    // Reader: dispatch responses to the matching pending client by id.
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) => break,
+            Ok(_) => {
+                if let Ok(mut response) = serde_json::from_str::<Value>(line.trim()) {
+                    if let Some(id) = response.get("id").and_then(Value::as_u64) {
+                        if let Some(sender) = endpoint.pending.lock().await.remove(&id) {
+                            let _ = sender.send(response.take());
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Relay endpoint '{}' read error: {}", name, e);
+                break;
+            }
+        }
+    }
+
+    registry.deregister(&name).await;
+    writer.abort();
+    Ok(())
+}
+
+/// Accept and service client connections.
+async fn accept_clients(
+    listener: TcpListener,
+    registry: Arc<RelayRegistry>,
+    validation: Arc<ValidationService>,
+) -> Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        debug!("Relay client connection from {}", peer);
+        let registry = registry.clone();
+        let validation = validation.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, registry, validation).await {
+                error!("Relay client connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// Read JSON-RPC requests from a client, validate, route to the named endpoint,
+/// await the endpoint's response, validate it, and write it back.
+async fn handle_client(
+    stream: TcpStream,
+    registry: Arc<RelayRegistry>,
+    validation: Arc<ValidationService>,
+) -> Result<()> {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut reader = BufReader::new(read_half);
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            break;
+        }
+        let request: Value = match serde_json::from_str(line.trim()) {
+            Ok(v) => v,
+            Err(e) => {
+                warn!("Relay client sent malformed JSON-RPC: {}", e);
+                continue;
+            }
+        };
+
+        // The target endpoint is named by an `X-Ramparts-Server`-style field.
+        // A missing selector is answered with a graceful error (like an unknown
+        // server below) rather than dropping the client connection.
+        let Some(server) = request.get("server").and_then(Value::as_str).map(str::to_string) else {
+            let err = validation
+                .create_error_response(&request, "request missing 'server' routing field");
+            write_line(&mut write_half, &err).await?;
+            continue;
+        };
+
+        // Validate the request before it leaves Ramparts.
+        let result = validation.validate_request(&request).await?;
+        if !result.allowed {
+            let blocked = validation.create_blocked_response(&request, &result);
+            write_line(&mut write_half, &blocked).await?;
+            continue;
+        }
+
+        let Some(endpoint) = registry.get(&server).await else {
+            let err = validation.create_error_response(
+                &request,
+                &format!("no relay endpoint registered as '{}'", server),
+            );
+            write_line(&mut write_half, &err).await?;
+            continue;
+        };
+
+        // Rewrite the id to a relay-unique value so concurrent clients of the
+        // same endpoint never collide, and remember the client's original id.
+        let client_id = request.get("id").cloned();
+        let relay_id = endpoint.allocate_id();
+        let mut forwarded = request.clone();
+        forwarded["id"] = Value::from(relay_id);
+
+        let (otx, orx) = oneshot::channel();
+        endpoint.pending.lock().await.insert(relay_id, otx);
+
+        if endpoint
+            .tx
+            .send(serde_json::to_string(&forwarded)?)
+            .await
+            .is_err()
+        {
+            endpoint.pending.lock().await.remove(&relay_id);
+            let err = validation
+                .create_error_response(&request, "relay endpoint disconnected mid-request");
+            write_line(&mut write_half, &err).await?;
+            continue;
+        }
+
+        // Await the correlated response, restore the client's id, and validate.
+        match orx.await {
+            Ok(mut response) => {
+                response["id"] = client_id.unwrap_or(Value::Null);
+                let result = validation.validate_response(&response).await?;
+                if result.allowed {
+                    write_line(&mut write_half, &response).await?;
+                } else {
+                    let blocked = validation.create_blocked_response(&response, &result);
+                    write_line(&mut write_half, &blocked).await?;
+                }
+            }
+            Err(_) => {
+                let err = validation
+                    .create_error_response(&request, "relay endpoint closed before responding");
+                write_line(&mut write_half, &err).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn write_line<W: AsyncWriteExt + Unpin>(writer: &mut W, value: &Value) -> Result<()> {
+    let mut bytes = serde_json::to_vec(value)?;
+    bytes.push(b'\n');
+    writer.write_all(&bytes).await?;
+    writer.flush().await?;
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use ramparts_common::{
+    anyhow::{anyhow, Result},
+    tracing::{debug, warn},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Operator-controlled allowlist of hosts/CIDRs that may be reached even when
+/// they resolve into an otherwise-forbidden range. Carried on `ProxyConfig`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SsrfAllowlist {
+    /// Exact host names (case-insensitive) that are always permitted.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// CIDR ranges whose addresses are always permitted, e.g. `10.0.5.0/24`.
+    #[serde(default)]
+    pub cidrs: Vec<String>,
+}
+
+/// Outcome of an SSRF check: either the request is denied with a reason, or it
+/// is permitted and carries the resolved address the caller must pin to.
+#[derive(Debug, Clone)]
+pub enum SsrfDecision {
+    /// The host resolves into a forbidden range (or all records were forbidden).
+    Blocked { host: String, reason: String },
+    /// The host is permitted; `pinned` is the validated address the subsequent
+    /// fetch must connect to so a later re-resolution cannot rebind to an
+    /// internal target.
+    Allowed { host: String, pinned: IpAddr },
+}
+
+/// Guards outbound URLs against SSRF by resolving the host once, validating
+/// every returned record, and pinning the address for the eventual fetch.
+#[derive(Debug, Clone, Default)]
+pub struct SsrfGuard {
+    allowlist: SsrfAllowlist,
+}
+
+impl SsrfGuard {
+    pub fn new(allowlist: SsrfAllowlist) -> Self {
+        Self { allowlist }
+    }
+
+    /// Walk `params` for any `uri`/`url` string field and validate each. Only
+    /// `http`/`https` URLs can drive an SSRF, so other schemes (and anything
+    /// that does not parse) are skipped rather than failing the request.
+    /// Returns the first block decision, or `None` when every URL is permitted
+    /// (or none are relevant).
+    pub async fn check_params(&self, params: &Value) -> Result<Option<SsrfDecision>> {
+        for raw in collect_urls(params) {
+            // Ignore non-URL strings and non-web schemes: a `file://` read or a
+            // custom-scheme URI is not an outbound network fetch we guard here.
+            match url::Url::parse(&raw) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                _ => continue,
+            }
+            match self.check_url(&raw).await {
+                Ok(SsrfDecision::Blocked { host, reason }) => {
+                    return Ok(Some(SsrfDecision::Blocked { host, reason }));
+                }
+                Ok(SsrfDecision::Allowed { .. }) => {}
+                // A host that cannot be resolved cannot be reached, so a
+                // resolution failure is a skip, not a request-failing error.
+                Err(e) => debug!("Skipping SSRF check for '{}': {}", raw, e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parse a single URL, resolve its host, and validate every A/AAAA record.
+    /// A host is permitted only if all resolved addresses are safe or fall in an
+    /// allowlisted CIDR (or the host is explicitly allowlisted); the returned
+    /// address is pinned for the fetch.
+    pub async fn check_url(&self, raw: &str) -> Result<SsrfDecision> {
+        let url = url::Url::parse(raw)
+            .map_err(|e| anyhow!("failed to parse URL '{}': {}", raw, e))?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("URL '{}' has no host", raw))?
+            .to_string();
+
+        if self.host_allowlisted(&host) {
+            debug!("Host '{}' is allowlisted, skipping SSRF range checks", host);
+            // Still resolve so the caller has an address to pin to.
+            if let Some(addr) = self.resolve(&host, url.port_or_known_default()).await?.into_iter().next() {
+                return Ok(SsrfDecision::Allowed { host, pinned: addr });
+            }
+            return Ok(SsrfDecision::Blocked {
+                host: host.clone(),
+                reason: format!("allowlisted host '{}' did not resolve to any address", host),
+            });
+        }
+
+        let addrs = self.resolve(&host, url.port_or_known_default()).await?;
+        if addrs.is_empty() {
+            return Ok(SsrfDecision::Blocked {
+                host: host.clone(),
+                reason: format!("host '{}' did not resolve to any address", host),
+            });
+        }
+
+        // Validate *every* record to defeat DNS rebinding: if any resolved
+        // address is forbidden we block, rather than racing on which one the
+        // later fetch happens to pick.
+        for addr in &addrs {
+            if let Some(range) = forbidden_range(addr) {
+                // Operators may opt specific internal ranges back in.
+                if self.allowlist.cidrs.iter().any(|c| cidr_contains(c, addr).unwrap_or(false)) {
+                    continue;
+                }
+                warn!("Blocked SSRF to host '{}' resolving to {} ({})", host, addr, range);
+                return Ok(SsrfDecision::Blocked {
+                    host: host.clone(),
+                    reason: format!(
+                        "host '{}' resolves to forbidden {} address {}",
+                        host, range, addr
+                    ),
+                });
+            }
+        }
+
+        // All records clean; pin the first so the fetch connects to a validated
+        // address instead of re-resolving.
+        Ok(SsrfDecision::Allowed {
+            host,
+            pinned: addrs[0],
+        })
+    }
+
+    fn host_allowlisted(&self, host: &str) -> bool {
+        let host_lc = host.to_ascii_lowercase();
+        if self.allowlist.hosts.iter().any(|h| h.to_ascii_lowercase() == host_lc) {
+            return true;
+        }
+        // A host given as a literal IP may match an allowlisted CIDR.
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return self
+                .allowlist
+                .cidrs
+                .iter()
+                .any(|c| cidr_contains(c, &ip).unwrap_or(false));
+        }
+        false
+    }
+
+    async fn resolve(&self, host: &str, port: Option<u16>) -> Result<Vec<IpAddr>> {
+        // A host that is already an IP literal needs no DNS lookup.
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+        let authority = format!("{}:{}", host, port.unwrap_or(80));
+        let addrs = tokio::net::lookup_host(authority)
+            .await
+            .map_err(|e| anyhow!("DNS resolution for '{}' failed: {}", host, e))?
+            .map(|sa| sa.ip())
+            .collect();
+        Ok(addrs)
+    }
+}
+
+/// Recursively collect every `uri`/`url` string value in a params object.
+fn collect_urls(value: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    collect_urls_into(value, &mut out);
+    out
+}
+
+fn collect_urls_into(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                let key = k.to_ascii_lowercase();
+                if (key == "uri" || key == "url") && v.is_string() {
+                    out.push(v.as_str().unwrap().to_string());
+                } else {
+                    collect_urls_into(v, out);
+                }
+            }
+        }
+        Value::Array(arr) => arr.iter().for_each(|v| collect_urls_into(v, out)),
+        _ => {}
+    }
+}
+
+/// Classify an address, returning a human-readable range name when it falls in
+/// a forbidden block and `None` when it is safe to reach.
+fn forbidden_range(addr: &IpAddr) -> Option<&'static str> {
+    match addr {
+        IpAddr::V4(v4) => forbidden_v4(v4),
+        IpAddr::V6(v6) => forbidden_v6(v6),
+    }
+}
+
+fn forbidden_v4(v4: &Ipv4Addr) -> Option<&'static str> {
+    let o = v4.octets();
+    if v4.is_loopback() {
+        Some("loopback")
+    } else if o[0] == 0 {
+        // 0.0.0.0/8 — "this host"; 0.0.0.0 routes to localhost on Linux.
+        Some("unspecified")
+    } else if o[0] == 10 {
+        Some("private")
+    } else if o[0] == 172 && (16..=31).contains(&o[1]) {
+        Some("private")
+    } else if o[0] == 192 && o[1] == 168 {
+        Some("private")
+    } else if o[0] == 169 && o[1] == 254 {
+        // Covers the cloud metadata address 169.254.169.254.
+        Some("link-local")
+    } else {
+        None
+    }
+}
+
+fn forbidden_v6(v6: &Ipv6Addr) -> Option<&'static str> {
+    // IPv4-mapped literals such as ::ffff:169.254.169.254 must be classified as
+    // the v4 address they carry, or they would bypass the v4 range checks.
+    if let Some(v4) = v6.to_ipv4_mapped() {
+        return forbidden_v4(&v4);
+    }
+    let seg = v6.segments();
+    if v6.is_loopback() {
+        Some("loopback")
+    } else if (seg[0] & 0xfe00) == 0xfc00 {
+        // fc00::/7 unique local addresses.
+        Some("ula")
+    } else if (seg[0] & 0xffc0) == 0xfe80 {
+        // fe80::/10 link-local.
+        Some("link-local")
+    } else {
+        None
+    }
+}
+
+/// Test whether an address is contained in a `a.b.c.d/prefix` (v4) or
+/// `addr/prefix` (v6) CIDR string.
+fn cidr_contains(cidr: &str, ip: &IpAddr) -> Result<bool> {
+    let (net, prefix) = cidr
+        .split_once('/')
+        .ok_or_else(|| anyhow!("invalid CIDR '{}'", cidr))?;
+    let prefix: u32 = prefix
+        .parse()
+        .map_err(|_| anyhow!("invalid CIDR prefix in '{}'", cidr))?;
+    match (net.parse::<IpAddr>()?, ip) {
+        (IpAddr::V4(net), IpAddr::V4(ip)) => {
+            if prefix > 32 {
+                return Err(anyhow!("invalid v4 prefix in '{}'", cidr));
+            }
+            let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix) };
+            Ok(u32::from(net) & mask == u32::from(*ip) & mask)
+        }
+        (IpAddr::V6(net), IpAddr::V6(ip)) => {
+            if prefix > 128 {
+                return Err(anyhow!("invalid v6 prefix in '{}'", cidr));
+            }
+            let mask = if prefix == 0 { 0 } else { u128::MAX << (128 - prefix) };
+            Ok(u128::from(net) & mask == u128::from(*ip) & mask)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_forbidden_ipv4_ranges() {
+        assert_eq!(forbidden_range(&"127.0.0.1".parse().unwrap()), Some("loopback"));
+        assert_eq!(forbidden_range(&"10.1.2.3".parse().unwrap()), Some("private"));
+        assert_eq!(forbidden_range(&"172.16.0.1".parse().unwrap()), Some("private"));
+        assert_eq!(forbidden_range(&"172.32.0.1".parse().unwrap()), None);
+        assert_eq!(forbidden_range(&"192.168.1.1".parse().unwrap()), Some("private"));
+        assert_eq!(
+            forbidden_range(&"169.254.169.254".parse().unwrap()),
+            Some("link-local")
+        );
+        assert_eq!(forbidden_range(&"0.0.0.0".parse().unwrap()), Some("unspecified"));
+        assert_eq!(forbidden_range(&"8.8.8.8".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_forbidden_ipv6_ranges() {
+        assert_eq!(forbidden_range(&"::1".parse().unwrap()), Some("loopback"));
+        assert_eq!(forbidden_range(&"fc00::1".parse().unwrap()), Some("ula"));
+        assert_eq!(forbidden_range(&"fe80::1".parse().unwrap()), Some("link-local"));
+        assert_eq!(forbidden_range(&"2606:4700::1".parse().unwrap()), None);
+        // IPv4-mapped literals must be classified as the v4 address they carry.
+        assert_eq!(
+            forbidden_range(&"::ffff:169.254.169.254".parse().unwrap()),
+            Some("link-local")
+        );
+        assert_eq!(forbidden_range(&"::ffff:127.0.0.1".parse().unwrap()), Some("loopback"));
+        assert_eq!(forbidden_range(&"::ffff:8.8.8.8".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_cidr_contains() {
+        let ip: IpAddr = "10.0.5.7".parse().unwrap();
+        assert!(cidr_contains("10.0.5.0/24", &ip).unwrap());
+        assert!(!cidr_contains("10.0.6.0/24", &ip).unwrap());
+    }
+
+    #[test]
+    fn test_collect_urls_nested() {
+        let params = json!({
+            "uri": "https://example.com/a",
+            "nested": {"url": "http://169.254.169.254/"},
+            "list": [{"uri": "file:///etc/passwd"}]
+        });
+        let urls = collect_urls(&params);
+        assert_eq!(urls.len(), 3);
+    }
+}
@@ -0,0 +1,150 @@
+//! Streaming validation for chunked MCP responses (Server-Sent Events /
+//! WebSocket frames).
+//!
+//! [`ValidationService::validate_response`](crate::ValidationService) assumes a
+//! single complete JSON value, but streamed tool output arrives incrementally.
+//! [`StreamValidator`] buffers and reassembles JSON-RPC messages across chunk
+//! boundaries and validates each complete message as it is emitted, so a
+//! blocked frame can be cut off mid-stream rather than after the whole body is
+//! collected.
+
+use ramparts_common::{anyhow::Result, tracing::warn};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::ValidationService;
+
+/// The disposition of a single reassembled frame.
+#[derive(Debug, Clone)]
+pub enum FrameDecision {
+    /// A validated, clean frame that should be forwarded to the client verbatim.
+    Pass(String),
+    /// A frame that failed validation; carries the JSON-RPC error response the
+    /// stream should emit before being torn down.
+    Blocked(Value),
+}
+
+/// Incrementally validates a stream of response frames. Feed raw chunks as they
+/// arrive; complete JSON-RPC messages are extracted and validated in order.
+/// Once any frame is blocked the validator short-circuits and rejects all
+/// further input.
+pub struct StreamValidator {
+    service: Arc<ValidationService>,
+    buffer: String,
+    blocked: bool,
+}
+
+impl StreamValidator {
+    pub(crate) fn new(service: Arc<ValidationService>) -> Self {
+        Self {
+            service,
+            buffer: String::new(),
+            blocked: false,
+        }
+    }
+
+    /// Feed the next chunk of bytes from the transport. Returns the decisions
+    /// for every complete message that became available, in order. The stream
+    /// should stop forwarding and close on the first [`FrameDecision::Blocked`].
+    pub async fn push_chunk(&mut self, chunk: &str) -> Result<Vec<FrameDecision>> {
+        if self.blocked {
+            return Ok(Vec::new());
+        }
+
+        self.buffer.push_str(chunk);
+        let mut out = Vec::new();
+
+        for message in self.drain_complete_messages() {
+            let decision = self.validate_message(message).await?;
+            let blocked = matches!(decision, FrameDecision::Blocked(_));
+            out.push(decision);
+            if blocked {
+                break;
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Validate a single, already-complete framed message as one unit. Use this
+    /// for transports (e.g. Content-Length framing) where each message arrives
+    /// whole and must not be re-split on internal newlines.
+    pub async fn push_message(&mut self, message: &str) -> Result<FrameDecision> {
+        if self.blocked {
+            // The stream was already torn down: reject instead of forwarding.
+            let value = serde_json::from_str::<Value>(message).unwrap_or(Value::Null);
+            return Ok(FrameDecision::Blocked(self.service.create_denial_response(
+                &value,
+                crate::validation_service::DenialCategory::PolicyDenied,
+                Some("stream already blocked by an earlier frame".to_string()),
+                None,
+                None,
+            )));
+        }
+        self.validate_message(message.to_string()).await
+    }
+
+    /// Validate one reassembled message, flipping the validator into its
+    /// short-circuit state on the first block.
+    async fn validate_message(&mut self, message: String) -> Result<FrameDecision> {
+        match serde_json::from_str::<Value>(&message) {
+            Ok(value) => {
+                let result = self.service.validate_response(&value).await?;
+                if result.allowed {
+                    Ok(FrameDecision::Pass(message))
+                } else {
+                    warn!("Streamed response frame blocked: {:?}", result.reason);
+                    self.blocked = true;
+                    Ok(FrameDecision::Blocked(
+                        self.service.create_blocked_response(&value, &result),
+                    ))
+                }
+            }
+            Err(e) => {
+                // Forward unparseable frames untouched, mirroring the stdio
+                // proxy's treatment of malformed payloads.
+                warn!("Failed to parse streamed frame, forwarding as-is: {}", e);
+                Ok(FrameDecision::Pass(message))
+            }
+        }
+    }
+
+    /// Whether the stream has been torn down by a blocked frame.
+    pub fn is_blocked(&self) -> bool {
+        self.blocked
+    }
+
+    /// Extract every complete message currently sitting in the buffer,
+    /// reassembling across chunk boundaries. Supports SSE (`data:` lines
+    /// terminated by a blank line) and a newline-delimited-JSON fallback.
+    fn drain_complete_messages(&mut self) -> Vec<String> {
+        let mut messages = Vec::new();
+
+        if self.buffer.contains("data:") {
+            // SSE: events are separated by a blank line.
+            while let Some(idx) = self.buffer.find("\n\n") {
+                let event: String = self.buffer.drain(..idx + 2).collect();
+                let payload: String = event
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(|l| l.trim_start())
+                    .collect::<Vec<_>>()
+                    .join("");
+                if !payload.is_empty() {
+                    messages.push(payload);
+                }
+            }
+        } else {
+            // Newline-delimited JSON: one message per line.
+            while let Some(idx) = self.buffer.find('\n') {
+                let line: String = self.buffer.drain(..idx + 1).collect();
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    messages.push(trimmed.to_string());
+                }
+            }
+        }
+
+        messages
+    }
+}
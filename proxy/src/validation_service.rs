@@ -1,12 +1,22 @@
 use ramparts_common::{anyhow::Result, tracing::{debug, info, warn, error}};
 use serde_json::{json, Value};
 use std::sync::Arc;
+use crate::audit::{AuditEvent, AuditLogger};
+use crate::keys::{KeyDecision, KeyStore};
+use crate::metrics::{BlockReason, RequestSource, ValidationMetrics};
+use crate::policy::{Action, PolicyDecision, PolicyEngine, RuleTarget};
+use crate::ssrf::{SsrfDecision, SsrfGuard};
 use crate::{JavelinClient, ProxyConfig};
 
 /// Unified validation service that handles all request/response validation
 pub struct ValidationService {
     javelin_client: Arc<JavelinClient>,
     config: ProxyConfig,
+    ssrf: SsrfGuard,
+    policy: PolicyEngine,
+    metrics: Arc<ValidationMetrics>,
+    audit: Option<Arc<AuditLogger>>,
+    keys: Option<Arc<KeyStore>>,
 }
 
 /// Validation result with detailed information
@@ -17,6 +27,10 @@ pub struct ValidationResult {
     pub confidence: Option<f64>,
     pub request_id: String,
     pub timestamp: String,
+    /// Why the request was blocked, when `allowed == false`. Lets the error
+    /// response distinguish a genuine policy denial from a fail-closed
+    /// infrastructure failure. Ignored when `allowed == true`.
+    pub category: DenialCategory,
 }
 
 /// Validation error with proper JSON-RPC formatting
@@ -27,16 +41,119 @@ pub struct ValidationError {
     pub data: Option<Value>,
 }
 
+/// Why a request did not reach the target, surfaced to the client so it can
+/// decide whether a retry is safe. Carried as `error.data.category`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenialCategory {
+    /// A deliberate guardrail decision — the client must NOT retry.
+    PolicyDenied,
+    /// A transient infrastructure failure (timeout, Javelin unreachable) — the
+    /// client MAY retry.
+    ValidationUnavailable,
+    /// The request was canceled before a decision was reached.
+    Canceled,
+}
+
+impl DenialCategory {
+    /// Stable application-level JSON-RPC error code (server-error range).
+    pub fn code(self) -> i32 {
+        match self {
+            DenialCategory::PolicyDenied => -32001,
+            DenialCategory::ValidationUnavailable => -32002,
+            DenialCategory::Canceled => -32003,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DenialCategory::PolicyDenied => "policy_denied",
+            DenialCategory::ValidationUnavailable => "validation_unavailable",
+            DenialCategory::Canceled => "canceled",
+        }
+    }
+
+    fn message(self) -> &'static str {
+        match self {
+            DenialCategory::PolicyDenied => "Request denied by Ramparts guardrail policy",
+            DenialCategory::ValidationUnavailable => "Ramparts validation temporarily unavailable",
+            DenialCategory::Canceled => "Request canceled before validation completed",
+        }
+    }
+}
+
 impl ValidationService {
     pub fn new(javelin_client: Arc<JavelinClient>, config: ProxyConfig) -> Self {
+        let ssrf = SsrfGuard::new(config.ssrf_allowlist.clone());
+        let audit = AuditLogger::from_config(&config.audit);
+        let policy = PolicyEngine::from_config(&config.policy).unwrap_or_else(|e| {
+            error!("Failed to load policy ({}); falling back to built-in defaults", e);
+            PolicyEngine::from_config(&crate::policy::PolicyConfig::default())
+                .expect("built-in default policy must compile")
+        });
+        let keys = KeyStore::from_config(&config.keys).unwrap_or_else(|e| {
+            error!("Failed to load API keys ({}); key enforcement disabled", e);
+            None
+        });
         Self {
             javelin_client,
             config,
+            ssrf,
+            policy,
+            metrics: Arc::new(ValidationMetrics::new()),
+            audit,
+            keys,
+        }
+    }
+
+    /// Access the API key store, when key enforcement is enabled.
+    pub fn key_store(&self) -> Option<Arc<KeyStore>> {
+        self.keys.clone()
+    }
+
+    /// Authorize a presented Ramparts API key for `request` *before* the
+    /// guardrails service is consulted. Returns a blocking [`ValidationResult`]
+    /// when the key is missing, expired, out of scope, or rate-limited, and
+    /// `None` when enforcement is disabled or the key passes.
+    pub fn authorize_key(&self, api_key: Option<&str>, request: &Value) -> Option<ValidationResult> {
+        let store = self.keys.as_ref()?;
+        match store.check(api_key, request) {
+            KeyDecision::Allow => None,
+            KeyDecision::Deny(reason) => {
+                warn!("Rejected request by key policy: {}", reason);
+                Some(ValidationResult {
+                    allowed: false,
+                    reason: Some(reason),
+                    confidence: Some(1.0),
+                    request_id: uuid::Uuid::new_v4().to_string(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    category: DenialCategory::PolicyDenied,
+                })
+            }
         }
     }
 
-    /// Validate a request with consistent error handling
+    /// Access the metrics registry (for snapshot/Prometheus export).
+    pub fn metrics(&self) -> Arc<ValidationMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Validate a request with consistent error handling. Traffic from the real
+    /// client path; internal callers use [`Self::validate_request_internal`].
     pub async fn validate_request(&self, request: &Value) -> Result<ValidationResult> {
+        self.validate_request_with_source(request, RequestSource::Client).await
+    }
+
+    /// Validate internally-generated traffic (health checks, cache-warm) so it
+    /// can be kept out of client-facing dashboards.
+    pub async fn validate_request_internal(&self, request: &Value) -> Result<ValidationResult> {
+        self.validate_request_with_source(request, RequestSource::Internal).await
+    }
+
+    async fn validate_request_with_source(
+        &self,
+        request: &Value,
+        source: RequestSource,
+    ) -> Result<ValidationResult> {
         debug!("Validating request with unified validation service");
 
         let request_id = uuid::Uuid::new_v4().to_string();
@@ -44,26 +161,36 @@ impl ValidationService {
 
         // Extract method for method-specific validation
         let method = request.get("method").and_then(|m| m.as_str()).unwrap_or("unknown");
-        debug!("Validating method: {}", method);
+        debug!(request_id = %request_id, method, "Validating method");
 
         // Apply method-specific validation rules
         if let Some(method_result) = self.validate_method_specific(request, method, &request_id, &timestamp).await? {
+            self.record_outcome(request, method, &method_result, source);
             return Ok(method_result);
         }
 
         // Check if we're in test mode (no Javelin API key)
         if self.config.javelin.api_key == "test-mode" {
             debug!("Test mode: allowing all requests without Javelin validation");
-            return Ok(ValidationResult {
+            let result = ValidationResult {
                 allowed: true,
                 reason: Some(format!("Test mode - {} validation bypassed", method)),
                 confidence: Some(1.0),
                 request_id,
                 timestamp,
-            });
+                category: DenialCategory::PolicyDenied,
+            };
+            self.record_outcome(request, method, &result, source);
+            return Ok(result);
         }
 
-        match self.javelin_client.validate_request(request).await {
+        // Time the Javelin round-trip so operators can see validation latency.
+        let started = std::time::Instant::now();
+        let javelin_result = self.javelin_client.validate_request(request).await;
+        self.metrics
+            .observe_javelin_latency(started.elapsed().as_secs_f64());
+
+        let result = match javelin_result {
             Ok(is_valid) => {
                 let result = ValidationResult {
                     allowed: is_valid,
@@ -75,18 +202,19 @@ impl ValidationService {
                     confidence: Some(if is_valid { 0.9 } else { 0.1 }),
                     request_id,
                     timestamp,
+                    category: DenialCategory::PolicyDenied,
                 };
 
                 if is_valid {
-                    info!("Request {} approved by validation service", result.request_id);
+                    info!(request_id = %result.request_id, "Request approved by validation service");
                 } else {
-                    warn!("Request {} blocked by validation service", result.request_id);
+                    warn!(request_id = %result.request_id, "Request blocked by validation service");
                 }
 
-                Ok(result)
+                result
             }
             Err(e) => {
-                error!("Validation error for request {}: {}", request_id, e);
+                error!(request_id = %request_id, "Validation error: {}", e);
 
                 // Apply fail-open/fail-closed policy
                 let allowed = self.config.javelin.fail_open;
@@ -97,22 +225,77 @@ impl ValidationService {
                 };
 
                 if allowed {
-                    warn!("Request {} allowed due to fail-open policy", request_id);
+                    warn!(request_id = %request_id, "Request allowed due to fail-open policy");
                 } else {
-                    error!("Request {} blocked due to fail-closed policy", request_id);
+                    error!(request_id = %request_id, "Request blocked due to fail-closed policy");
                 }
 
-                Ok(ValidationResult {
+                // A fail-closed block is an infrastructure failure, not a
+                // guardrail decision: tag it so the client sees a retryable
+                // `validation_unavailable` error rather than a `policy_denied`.
+                let category = if allowed {
+                    DenialCategory::PolicyDenied
+                } else {
+                    DenialCategory::ValidationUnavailable
+                };
+                ValidationResult {
                     allowed,
                     reason: Some(reason),
                     confidence: Some(0.0),
                     request_id,
                     timestamp,
-                })
+                    category,
+                }
             }
+        };
+
+        self.record_outcome(request, method, &result, source);
+        Ok(result)
+    }
+
+    /// Update the metrics registry and emit an audit event for a finished
+    /// decision.
+    fn record_outcome(
+        &self,
+        request: &Value,
+        method: &str,
+        result: &ValidationResult,
+        source: RequestSource,
+    ) {
+        if result.allowed {
+            self.metrics.record_allow(method, source);
+        } else {
+            let reason = result
+                .reason
+                .as_deref()
+                .map(BlockReason::classify)
+                .unwrap_or(BlockReason::JavelinDenied);
+            self.metrics.record_block(method, reason, source);
+        }
+
+        if let Some(audit) = &self.audit {
+            let params = request
+                .get("params")
+                .map(crate::logging::sanitize_json_for_log)
+                .unwrap_or(Value::Null);
+            audit.emit(AuditEvent {
+                request_id: result.request_id.clone(),
+                timestamp: result.timestamp.clone(),
+                method: method.to_string(),
+                allowed: result.allowed,
+                matched_rule: result.reason.clone(),
+                confidence: result.confidence,
+                params,
+            });
         }
     }
 
+    /// Produce a [`StreamValidator`](crate::stream::StreamValidator) that runs
+    /// response validation incrementally over chunked SSE/WebSocket frames.
+    pub fn stream_validator(self: &Arc<Self>) -> crate::stream::StreamValidator {
+        crate::stream::StreamValidator::new(self.clone())
+    }
+
     /// Validate a response (optional, for response filtering)
     pub async fn validate_response(&self, response: &Value) -> Result<ValidationResult> {
         debug!("Validating response with unified validation service");
@@ -129,37 +312,56 @@ impl ValidationService {
         Ok(result)
     }
 
-    /// Create a JSON-RPC error response for blocked requests
+    /// Create a JSON-RPC error response for a blocked request, carrying the
+    /// category recorded on the result so a fail-closed infrastructure failure
+    /// (`category = "validation_unavailable"`, retryable) is distinguishable
+    /// from a deliberate policy denial (`category = "policy_denied"`).
     pub fn create_blocked_response(&self, original_request: &Value, validation_result: &ValidationResult) -> Value {
-        json!({
-            "jsonrpc": "2.0",
-            "id": original_request.get("id"),
-            "error": {
-                "code": -32600,
-                "message": "Request blocked by Javelin Guardrails",
-                "data": {
-                    "reason": validation_result.reason,
-                    "confidence": validation_result.confidence,
-                    "request_id": validation_result.request_id,
-                    "timestamp": validation_result.timestamp,
-                    "blocked_by": "ramparts-proxy"
-                }
-            }
-        })
+        self.create_denial_response(
+            original_request,
+            validation_result.category,
+            validation_result.reason.clone(),
+            validation_result.confidence,
+            Some(&validation_result.request_id),
+        )
     }
 
-    /// Create a JSON-RPC error response for validation failures
+    /// Create a JSON-RPC error response for a validation infrastructure failure.
+    /// Clients may safely retry these (`category = "validation_unavailable"`).
     pub fn create_error_response(&self, original_request: &Value, error_message: &str) -> Value {
+        self.create_denial_response(
+            original_request,
+            DenialCategory::ValidationUnavailable,
+            Some(error_message.to_string()),
+            None,
+            None,
+        )
+    }
+
+    /// Build a categorized JSON-RPC error so clients can tell a guardrail denial
+    /// apart from a transient failure or cancellation and retry accordingly.
+    pub fn create_denial_response(
+        &self,
+        original_request: &Value,
+        category: DenialCategory,
+        reason: Option<String>,
+        confidence: Option<f64>,
+        request_id: Option<&str>,
+    ) -> Value {
         json!({
             "jsonrpc": "2.0",
             "id": original_request.get("id"),
             "error": {
-                "code": -32603,
-                "message": "Internal validation error",
+                "code": category.code(),
+                "message": category.message(),
                 "data": {
-                    "error": error_message,
+                    "category": category.as_str(),
+                    "reason": reason,
+                    "confidence": confidence,
+                    "request_id": request_id,
+                    "retryable": category == DenialCategory::ValidationUnavailable,
                     "timestamp": chrono::Utc::now().to_rfc3339(),
-                    "service": "ramparts-proxy"
+                    "blocked_by": "ramparts-proxy"
                 }
             }
         })
@@ -208,31 +410,54 @@ impl ValidationService {
         match method {
             "tools/call" => {
                 debug!("Applying tools/call specific validation rules");
-                // Check for dangerous tool calls
                 if let Some(params) = request.get("params") {
                     if let Some(name) = params.get("name").and_then(|n| n.as_str()) {
-                        // Block dangerous tools
-                        if self.is_dangerous_tool(name) {
-                            warn!("Blocked dangerous tool call: {}", name);
-                            return Ok(Some(ValidationResult {
-                                allowed: false,
-                                reason: Some(format!("Dangerous tool '{}' blocked by security policy", name)),
-                                confidence: Some(0.9),
-                                request_id: request_id.to_string(),
-                                timestamp: timestamp.to_string(),
-                            }));
+                        // Evaluate the tool name against the loaded policy.
+                        if let Some(decision) = self.policy.evaluate(RuleTarget::ToolName, name) {
+                            if let Some(result) = self.apply_block(
+                                &decision,
+                                format!("Tool '{}' blocked by policy rule '{}'", name, decision.rule_name),
+                                request_id,
+                                timestamp,
+                            ) {
+                                return Ok(Some(result));
+                            }
                         }
 
-                        // Check tool arguments for injection patterns
+                        // Evaluate tool arguments for injection patterns.
                         if let Some(args) = params.get("arguments") {
-                            if self.contains_injection_patterns(args) {
-                                warn!("Blocked tool call with injection patterns: {}", name);
+                            let args_str = args.to_string();
+                            if let Some(decision) =
+                                self.policy.evaluate(RuleTarget::ToolArguments, &args_str)
+                            {
+                                if let Some(result) = self.apply_block(
+                                    &decision,
+                                    format!(
+                                        "Tool '{}' arguments blocked by policy rule '{}'",
+                                        name, decision.rule_name
+                                    ),
+                                    request_id,
+                                    timestamp,
+                                ) {
+                                    return Ok(Some(result));
+                                }
+                            }
+
+                            // Block outbound-network abuse (SSRF) via URLs in args.
+                            if let Some(SsrfDecision::Blocked { host, reason }) =
+                                self.ssrf.check_params(args).await?
+                            {
+                                warn!("Blocked SSRF in tool call '{}': {}", name, reason);
                                 return Ok(Some(ValidationResult {
                                     allowed: false,
-                                    reason: Some(format!("Tool '{}' arguments contain injection patterns", name)),
-                                    confidence: Some(0.8),
+                                    reason: Some(format!(
+                                        "Tool '{}' arguments target blocked host '{}': {}",
+                                        name, host, reason
+                                    )),
+                                    confidence: Some(0.9),
                                     request_id: request_id.to_string(),
                                     timestamp: timestamp.to_string(),
+                                    category: DenialCategory::PolicyDenied,
                                 }));
                             }
                         }
@@ -241,36 +466,59 @@ impl ValidationService {
             }
             "resources/read" => {
                 debug!("Applying resources/read specific validation rules");
-                // Check for path traversal attempts
                 if let Some(params) = request.get("params") {
                     if let Some(uri) = params.get("uri").and_then(|u| u.as_str()) {
-                        if self.contains_path_traversal(uri) {
-                            warn!("Blocked resource read with path traversal: {}", uri);
-                            return Ok(Some(ValidationResult {
-                                allowed: false,
-                                reason: Some(format!("Resource URI '{}' contains path traversal patterns", uri)),
-                                confidence: Some(0.9),
-                                request_id: request_id.to_string(),
-                                timestamp: timestamp.to_string(),
-                            }));
+                        if let Some(decision) = self.policy.evaluate(RuleTarget::ResourceUri, uri) {
+                            if let Some(result) = self.apply_block(
+                                &decision,
+                                format!(
+                                    "Resource URI '{}' blocked by policy rule '{}'",
+                                    uri, decision.rule_name
+                                ),
+                                request_id,
+                                timestamp,
+                            ) {
+                                return Ok(Some(result));
+                            }
                         }
                     }
+
+                    // Resolve and validate any URL in the read params against the
+                    // SSRF guard so a later dereference can't reach internal hosts.
+                    if let Some(SsrfDecision::Blocked { host, reason }) =
+                        self.ssrf.check_params(params).await?
+                    {
+                        warn!("Blocked resource read targeting '{}': {}", host, reason);
+                        return Ok(Some(ValidationResult {
+                            allowed: false,
+                            reason: Some(format!(
+                                "Resource read targets blocked host '{}': {}",
+                                host, reason
+                            )),
+                            confidence: Some(0.9),
+                            request_id: request_id.to_string(),
+                            timestamp: timestamp.to_string(),
+                            category: DenialCategory::PolicyDenied,
+                        }));
+                    }
                 }
             }
             "prompts/get" => {
                 debug!("Applying prompts/get specific validation rules");
-                // Check for prompt injection attempts
                 if let Some(params) = request.get("params") {
                     if let Some(name) = params.get("name").and_then(|n| n.as_str()) {
-                        if self.contains_prompt_injection(name) {
-                            warn!("Blocked prompt with injection patterns: {}", name);
-                            return Ok(Some(ValidationResult {
-                                allowed: false,
-                                reason: Some(format!("Prompt '{}' contains injection patterns", name)),
-                                confidence: Some(0.8),
-                                request_id: request_id.to_string(),
-                                timestamp: timestamp.to_string(),
-                            }));
+                        if let Some(decision) = self.policy.evaluate(RuleTarget::PromptName, name) {
+                            if let Some(result) = self.apply_block(
+                                &decision,
+                                format!(
+                                    "Prompt '{}' blocked by policy rule '{}'",
+                                    name, decision.rule_name
+                                ),
+                                request_id,
+                                timestamp,
+                            ) {
+                                return Ok(Some(result));
+                            }
                         }
                     }
                 }
@@ -283,52 +531,33 @@ impl ValidationService {
         Ok(None) // No method-specific blocking, continue with general validation
     }
 
-    /// Check if a tool name is considered dangerous
-    fn is_dangerous_tool(&self, tool_name: &str) -> bool {
-        let dangerous_tools = [
-            "exec", "shell", "bash", "cmd", "powershell", "eval", "system",
-            "subprocess", "popen", "spawn", "fork", "kill", "rm", "del",
-            "format", "fdisk", "mkfs", "dd", "nc", "netcat", "telnet",
-            "curl_exec", "wget_exec", "download_exec"
-        ];
-
-        dangerous_tools.iter().any(|&dangerous| {
-            tool_name.to_lowercase().contains(dangerous)
-        })
-    }
-
-    /// Check for injection patterns in tool arguments
-    fn contains_injection_patterns(&self, args: &Value) -> bool {
-        let args_str = args.to_string().to_lowercase();
-        let injection_patterns = [
-            "; ", "| ", "& ", "$(", "`", "&&", "||", "../", "..\\",
-            "rm -", "del ", "format ", "fdisk", "mkfs", "dd if=",
-            "curl ", "wget ", "nc ", "netcat", "telnet", "ssh ",
-            "base64", "eval", "exec", "system", "popen"
-        ];
-
-        injection_patterns.iter().any(|&pattern| args_str.contains(pattern))
-    }
-
-    /// Check for path traversal patterns
-    fn contains_path_traversal(&self, uri: &str) -> bool {
-        let uri_lower = uri.to_lowercase();
-        uri_lower.contains("../") || uri_lower.contains("..\\") ||
-        uri_lower.contains("%2e%2e") || uri_lower.contains("....") ||
-        uri_lower.contains("/etc/") || uri_lower.contains("\\windows\\") ||
-        uri_lower.contains("/proc/") || uri_lower.contains("/sys/")
-    }
-
-    /// Check for prompt injection patterns
-    fn contains_prompt_injection(&self, prompt_name: &str) -> bool {
-        let prompt_lower = prompt_name.to_lowercase();
-        let injection_patterns = [
-            "ignore", "forget", "disregard", "override", "bypass", "jailbreak",
-            "system:", "assistant:", "user:", "human:", "ai:", "chatgpt:",
-            "\\n\\n", "---", "###", "```", "exec", "eval", "script"
-        ];
-
-        injection_patterns.iter().any(|&pattern| prompt_lower.contains(pattern))
+    /// Turn a policy decision into a blocking [`ValidationResult`]. Returns
+    /// `None` for `flag-and-forward` rules, which are allowed to proceed to
+    /// Javelin rather than being rejected outright.
+    fn apply_block(
+        &self,
+        decision: &PolicyDecision,
+        reason: String,
+        request_id: &str,
+        timestamp: &str,
+    ) -> Option<ValidationResult> {
+        match decision.action {
+            Action::Block => {
+                warn!("{}", reason);
+                Some(ValidationResult {
+                    allowed: false,
+                    reason: Some(reason),
+                    confidence: Some(decision.confidence),
+                    request_id: request_id.to_string(),
+                    timestamp: timestamp.to_string(),
+                    category: DenialCategory::PolicyDenied,
+                })
+            }
+            Action::Flag => {
+                debug!("Policy rule '{}' flagged request, forwarding to Javelin", decision.rule_name);
+                None
+            }
+        }
     }
 }
 